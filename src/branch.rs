@@ -0,0 +1,279 @@
+use crate::git::{self, Branch};
+use crate::prompt::{Confirm, Prompt, PromptAction};
+use crate::{render_message, App};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+///
+/// What the screen waits for before running git.
+///
+enum Pending {
+    Create(Prompt),
+    Rename(Prompt),
+    Upstream(Prompt),
+    Delete(Confirm),
+}
+
+pub enum BranchesAction {
+    Stay,
+    Back,
+}
+
+///
+/// The local and remote-tracking branches of the repository.
+///
+pub struct Branches {
+    branches: Vec<Branch>,
+    selected: usize,
+    pending: Option<Pending>,
+    notice: Option<String>,
+    message: Option<String>,
+}
+
+impl Branches {
+    pub fn new() -> Self {
+        let branches = git::branches();
+        let selected = branches
+            .iter()
+            .position(|branch| branch.current)
+            .unwrap_or(0);
+        Self {
+            branches,
+            selected,
+            pending: None,
+            notice: None,
+            message: None,
+        }
+    }
+
+    fn branch(&self) -> Option<&Branch> {
+        self.branches.get(self.selected)
+    }
+
+    ///
+    /// Run git then reload the branches, `done` is shown when git says
+    /// nothing.
+    ///
+    fn git(&mut self, args: &[&str], done: &str) {
+        match git::notice(args, done) {
+            Ok(notice) => self.notice = Some(notice),
+            Err(output) => self.message = Some(output),
+        }
+        let name = self.branch().map(|branch| branch.name.clone());
+        self.branches = git::branches();
+        self.selected = name
+            .and_then(|name| {
+                self.branches
+                    .iter()
+                    .position(|branch| branch.name.eq(&name))
+            })
+            .unwrap_or(self.selected)
+            .min(self.branches.len().saturating_sub(1));
+    }
+
+    fn checkout(&mut self) {
+        let Some(branch) = self.branch() else {
+            return;
+        };
+        let name = branch.name.clone();
+        if branch.remote {
+            self.git(
+                &["switch", "--track", &name],
+                "Switched to a new tracking branch.",
+            );
+        } else {
+            self.git(&["switch", &name], &format!("Switched to {name}."));
+        }
+    }
+
+    ///
+    /// Delete the selected local branch, asking first when it is not merged.
+    ///
+    fn delete(&mut self) {
+        let Some(branch) = self.branch().filter(|branch| !branch.remote) else {
+            self.notice = Some(String::from("Only local branches can be deleted."));
+            return;
+        };
+        let name = branch.name.clone();
+        if git::merged(&name) {
+            self.git(&["branch", "-d", &name], &format!("Deleted {name}."));
+        } else {
+            self.pending = Some(Pending::Delete(Confirm::new(&format!(
+                "{name} is not merged in the current branch, its commits may be lost. Delete it anyway?"
+            ))));
+        }
+    }
+
+    fn local(&mut self) -> Option<String> {
+        let branch = self.branch()?;
+        if branch.remote {
+            self.notice = Some(String::from("Select a local branch."));
+            return None;
+        }
+        Some(branch.name.clone())
+    }
+
+    fn submit(&mut self, pending: &Pending, value: &str) {
+        let Some(branch) = self.branch() else {
+            return;
+        };
+        let name = branch.name.clone();
+        match pending {
+            Pending::Create(_) => {
+                self.git(
+                    &["branch", value, &name],
+                    &format!("Created {value} from {name}."),
+                );
+            }
+            Pending::Rename(_) => {
+                self.git(
+                    &["branch", "-m", &name, value],
+                    &format!("Renamed {name} to {value}."),
+                );
+            }
+            Pending::Upstream(_) => self.git(
+                &["branch", &format!("--set-upstream-to={value}"), &name],
+                &format!("{name} now tracks {value}."),
+            ),
+            Pending::Delete(_) => {
+                self.git(&["branch", "-D", &name], &format!("Deleted {name}."));
+            }
+        }
+    }
+
+    fn handle_pending(&mut self, mut pending: Pending, key: KeyEvent) {
+        let answer = match &mut pending {
+            Pending::Create(prompt) | Pending::Rename(prompt) | Pending::Upstream(prompt) => {
+                match prompt.handle(key) {
+                    PromptAction::Stay => None,
+                    PromptAction::Cancel => Some(None),
+                    PromptAction::Submit(value) if value.is_empty() => Some(None),
+                    PromptAction::Submit(value) => match git::branch_name(&value) {
+                        Ok(value) => Some(Some(value)),
+                        Err(output) => {
+                            self.message = Some(output);
+                            Some(None)
+                        }
+                    },
+                }
+            }
+            Pending::Delete(_) => Confirm::handle(key).map(|yes| yes.then(String::new)),
+        };
+        match answer {
+            None => self.pending = Some(pending),
+            Some(None) => {}
+            Some(Some(value)) => self.submit(&pending, &value),
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> BranchesAction {
+        self.notice = None;
+        if self.message.take().is_some() {
+            return BranchesAction::Stay;
+        }
+        if let Some(pending) = self.pending.take() {
+            self.handle_pending(pending, key);
+            return BranchesAction::Stay;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return BranchesAction::Back,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.branches.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Enter | KeyCode::Char('c') => self.checkout(),
+            KeyCode::Char('n') => {
+                if let Some(branch) = self.branch() {
+                    let title = format!("New branch from {}", branch.name);
+                    self.pending = Some(Pending::Create(Prompt::new(&title, "")));
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(name) = self.local() {
+                    let title = format!("Rename {name}");
+                    self.pending = Some(Pending::Rename(Prompt::new(&title, &name)));
+                }
+            }
+            KeyCode::Char('u') => {
+                if let Some(name) = self.local() {
+                    let upstream = self
+                        .branch()
+                        .map(|branch| branch.upstream.clone())
+                        .filter(|upstream| !upstream.is_empty())
+                        .unwrap_or_else(|| format!("origin/{name}"));
+                    let title = format!("Upstream of {name}");
+                    self.pending = Some(Pending::Upstream(Prompt::new(&title, &upstream)));
+                }
+            }
+            KeyCode::Char('d') => self.delete(),
+            _ => {}
+        }
+        BranchesAction::Stay
+    }
+
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_type(BorderType::Rounded)
+            .title(" Branches ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(" ⏎ checkout  n new  r rename  d delete  u upstream  esc back ")
+            .title_alignment(Alignment::Center)
+            .style(app.theme.text());
+        let inner = block.inner(f.area());
+        f.render_widget(block, f.area());
+        let [list, notice] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let rows: Vec<Row> = self
+            .branches
+            .iter()
+            .map(|branch| {
+                let row = Row::new(vec![
+                    String::from(if branch.current { "*" } else { "" }),
+                    branch.name.clone(),
+                    branch.upstream.clone(),
+                    branch.commit.clone(),
+                    branch.date.clone(),
+                    branch.subject.clone(),
+                ]);
+                if branch.remote {
+                    row.style(app.theme.inactive())
+                } else {
+                    row
+                }
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(9),
+                Constraint::Length(16),
+                Constraint::Fill(4),
+            ],
+        )
+        .header(Row::new([
+            "", "Branch", "Upstream", "Commit", "Date", "Subject",
+        ]))
+        .highlight_style(app.theme.selection());
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        f.render_stateful_widget(table, list, &mut state);
+        if let Some(text) = &self.notice {
+            f.render_widget(Paragraph::new(text.as_str()), notice);
+        }
+        match &self.pending {
+            Some(Pending::Create(prompt) | Pending::Rename(prompt) | Pending::Upstream(prompt)) => {
+                prompt.render(f, &app.theme);
+            }
+            Some(Pending::Delete(confirm)) => confirm.render(f, &app.theme),
+            None => {}
+        }
+        if let Some(message) = &self.message {
+            render_message(f, message);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::git;
+use crate::message::{parse, Trailers};
+use crate::prompt::{Picker, PickerAction};
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+use tui_textarea::{CursorMove, TextArea};
+
+///
+/// The fields completed with the people of the history, as `(page, field)`.
+///
+pub const PEOPLE: [(usize, usize); 2] = [(6, 0), (6, 1)];
+
+///
+/// The authors of the history then the people named in the authors and
+/// testers of its commit messages.
+///
+pub fn people(trailers: &Trailers) -> Vec<String> {
+    let mut people = git::authors();
+    for message in git::messages(1000) {
+        for (page, field, text) in parse(&message, trailers) {
+            if !PEOPLE.contains(&(page, field)) {
+                continue;
+            }
+            for person in text.lines().map(str::trim) {
+                if !person.is_empty() && !people.iter().any(|p| p.eq(person)) {
+                    people.push(person.to_string());
+                }
+            }
+        }
+    }
+    people
+}
+
+pub enum CompletionAction {
+    Stay,
+    Close,
+    Complete(String),
+}
+
+///
+/// The candidates matching the line under the cursor.
+///
+pub struct Completion {
+    candidates: Vec<String>,
+    picker: Picker,
+}
+
+impl Completion {
+    pub fn new(candidates: Vec<String>, textarea: &TextArea<'_>) -> Self {
+        let typed = textarea.lines()[textarea.cursor().0].trim().to_lowercase();
+        Self {
+            candidates: candidates
+                .into_iter()
+                .filter(|candidate| candidate.to_lowercase().contains(&typed))
+                .collect(),
+            picker: Picker::new("Complete", " ⏎ complete  esc close ", 70),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    ///
+    /// Tab completes like enter.
+    ///
+    pub fn handle(&mut self, key: KeyEvent) -> CompletionAction {
+        let key = if key.code.eq(&KeyCode::Tab) {
+            KeyEvent::new(KeyCode::Enter, key.modifiers)
+        } else {
+            key
+        };
+        match self.picker.handle(key, self.candidates.len()) {
+            PickerAction::Stay => CompletionAction::Stay,
+            PickerAction::Close => CompletionAction::Close,
+            PickerAction::Choose(i) => CompletionAction::Complete(self.candidates[i].clone()),
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let items: Vec<ListItem> = self
+            .candidates
+            .iter()
+            .map(|candidate| ListItem::new(candidate.as_str()))
+            .collect();
+        self.picker.render(f, theme, items);
+    }
+}
+
+///
+/// Replace the line under the cursor with `text`.
+///
+pub fn complete(textarea: &mut TextArea<'_>, text: &str) {
+    textarea.move_cursor(CursorMove::Head);
+    textarea.delete_line_by_end();
+    textarea.insert_str(text);
+}
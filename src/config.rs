@@ -0,0 +1,199 @@
+use crate::git;
+use crate::keymap::{Action, Preset};
+use crate::snippet::Snippet;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+///
+/// The user configuration, read from `$XDG_CONFIG_HOME/rei/config.toml`.
+///
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub keys: Keys,
+    pub editor: Editor,
+    pub theme: ThemeChoice,
+    pub themes: HashMap<String, ThemeColors>,
+    pub accessibility: Accessibility,
+    pub form: Form,
+    pub commit: Commit,
+    pub spell: Spell,
+    pub profiles: BTreeMap<String, ProfilePages>,
+    pub snippets: Vec<Snippet>,
+    pub defaults: BTreeMap<String, String>,
+    pub trailers: BTreeMap<String, String>,
+}
+
+///
+/// The repository configuration, read from `.rei.toml` at the root of the
+/// working tree and shared by its contributors.
+///
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepoConfig {
+    pub snippets: Vec<Snippet>,
+    pub defaults: BTreeMap<String, String>,
+    pub trailers: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Form {
+    pub profile: Option<String>,
+}
+
+///
+/// The spell checking of the prose fields, `dictionary` is the path of a
+/// Hunspell dictionary without its extension.
+///
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Spell {
+    pub enabled: bool,
+    pub dictionary: Option<String>,
+}
+
+impl Default for Spell {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dictionary: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Commit {
+    pub signoff: bool,
+}
+
+///
+/// A user profile, the pages are named by their title.
+///
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfilePages {
+    pub description: Option<String>,
+    pub pages: Vec<String>,
+}
+
+///
+/// No blinking, states spelled out in the titles and a single column layout.
+///
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Accessibility {
+    pub enabled: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeChoice {
+    pub name: String,
+}
+
+impl Default for ThemeChoice {
+    fn default() -> Self {
+        Self {
+            name: String::from("dark"),
+        }
+    }
+}
+
+///
+/// A user theme, the colours it does not define come from `base`.
+///
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ThemeColors {
+    pub base: Option<String>,
+    pub text: Option<String>,
+    pub inactive: Option<String>,
+    pub too_long: Option<String>,
+    pub long: Option<String>,
+    pub medium: Option<String>,
+    pub short: Option<String>,
+    pub selection: Option<String>,
+    pub selected: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Editor {
+    pub mode: EditorMode,
+}
+
+#[derive(Deserialize, Copy, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EditorMode {
+    #[default]
+    Default,
+    Vim,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keys {
+    pub preset: Preset,
+    pub bindings: HashMap<Action, KeyList>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            Self::One(key) => vec![key.as_str()],
+            Self::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("rei").join("config.toml"))
+    }
+
+    ///
+    /// Load the user configuration, a missing file gives the defaults, then
+    /// add the snippets, defaults and trailers of the repository configuration.
+    ///
+    /// # Errors
+    ///
+    /// On unreadable or invalid configuration file.
+    ///
+    pub fn load() -> Result<Self, String> {
+        let mut config: Self = match Self::path().filter(|path| path.exists()) {
+            Some(path) => read(&path)?,
+            None => Self::default(),
+        };
+        if let Some(path) = git::toplevel()
+            .map(|root| root.join(".rei.toml"))
+            .filter(|path| path.exists())
+        {
+            let repo: RepoConfig = read(&path)?;
+            config.snippets.extend(repo.snippets);
+            config.defaults.extend(repo.defaults);
+            config.trailers.extend(repo.trailers);
+        }
+        Ok(config)
+    }
+}
+
+fn read<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    toml::from_str(&content).map_err(|e| format!("{}: {e}", path.display()))
+}
@@ -0,0 +1,433 @@
+use crate::git::{self, Operation};
+use crate::prompt::Confirm;
+use crate::{external, render_message, App};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal};
+use std::fs;
+use std::io::Stdout;
+use std::path::PathBuf;
+
+///
+/// A conflict of a file, between its `<<<<<<<` and `>>>>>>>` lines.
+///
+struct Hunk {
+    start: usize,
+    end: usize,
+    ours: Vec<String>,
+    ///
+    /// Only written with the `diff3` or `zdiff3` conflict styles.
+    ///
+    base: Option<Vec<String>>,
+    theirs: Vec<String>,
+}
+
+#[derive(Copy, Clone)]
+enum Side {
+    Ours,
+    Theirs,
+    Both,
+}
+
+///
+/// The conflicts of a file, its lines keep their line ending.
+///
+fn hunks(lines: &[String]) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+    let mut part = 0;
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            current = Some(Hunk {
+                start: i,
+                end: i,
+                ours: Vec::new(),
+                base: None,
+                theirs: Vec::new(),
+            });
+            part = 0;
+            continue;
+        }
+        let Some(hunk) = current.as_mut() else {
+            continue;
+        };
+        if line.starts_with("|||||||") {
+            hunk.base = Some(Vec::new());
+            part = 1;
+        } else if line.starts_with("=======") {
+            part = 2;
+        } else if line.starts_with(">>>>>>>") {
+            hunk.end = i;
+            hunks.extend(current.take());
+        } else {
+            match (part, hunk.base.as_mut()) {
+                (0, _) => hunk.ours.push(line.clone()),
+                (1, Some(base)) => base.push(line.clone()),
+                _ => hunk.theirs.push(line.clone()),
+            }
+        }
+    }
+    hunks
+}
+
+///
+/// Replace the conflict by the chosen side.
+///
+fn resolve(lines: &mut Vec<String>, hunk: &Hunk, side: Side) {
+    let chosen = match side {
+        Side::Ours => hunk.ours.clone(),
+        Side::Theirs => hunk.theirs.clone(),
+        Side::Both => [hunk.ours.clone(), hunk.theirs.clone()].concat(),
+    };
+    lines.splice(hunk.start..=hunk.end, chosen);
+}
+
+pub enum ConflictsAction {
+    Stay,
+    Back,
+    ///
+    /// Write the merge commit in the form, with the message prepared by git.
+    ///
+    Form(String),
+}
+
+///
+/// The files in conflict after a merge, a rebase, a cherry-pick or a revert,
+/// resolved one conflict at a time.
+///
+pub struct Conflicts {
+    operation: Option<Operation>,
+    root: PathBuf,
+    files: Vec<String>,
+    selected: usize,
+    lines: Vec<String>,
+    hunks: Vec<Hunk>,
+    hunk: usize,
+    abort: Option<Confirm>,
+    notice: Option<String>,
+    message: Option<String>,
+}
+
+impl Conflicts {
+    pub fn new() -> Self {
+        let mut conflicts = Self {
+            operation: git::operation(),
+            root: git::toplevel().unwrap_or_else(|| PathBuf::from(".")),
+            files: Vec::new(),
+            selected: 0,
+            lines: Vec::new(),
+            hunks: Vec::new(),
+            hunk: 0,
+            abort: None,
+            notice: None,
+            message: None,
+        };
+        conflicts.reload();
+        conflicts
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.files
+            .get(self.selected)
+            .map(|file| self.root.join(file))
+    }
+
+    fn reload(&mut self) {
+        self.operation = git::operation();
+        self.files = git::conflicts();
+        self.select(self.selected);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.files.len().saturating_sub(1));
+        self.lines = self
+            .path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| text.split_inclusive('\n').map(String::from).collect())
+            .unwrap_or_default();
+        self.hunks = hunks(&self.lines);
+        self.hunk = self.hunk.min(self.hunks.len().saturating_sub(1));
+    }
+
+    fn choose(&mut self, side: Side) {
+        let (Some(path), Some(hunk)) = (self.path(), self.hunks.get(self.hunk)) else {
+            return;
+        };
+        resolve(&mut self.lines, hunk, side);
+        if let Err(e) = fs::write(&path, self.lines.concat()) {
+            self.message = Some(format!("{}: {e}", path.display()));
+        }
+        self.select(self.selected);
+    }
+
+    fn git(&mut self, args: &[&str], done: &str) {
+        match git::run(args) {
+            Ok(output) => {
+                self.notice = Some(
+                    output
+                        .lines()
+                        .last()
+                        .filter(|line| !line.is_empty())
+                        .unwrap_or(done)
+                        .to_string(),
+                );
+            }
+            Err(output) => self.message = Some(output),
+        }
+        self.reload();
+    }
+
+    fn mark(&mut self) {
+        let Some(file) = self.files.get(self.selected).cloned() else {
+            return;
+        };
+        if self.hunks.is_empty() {
+            let root = self.root.display().to_string();
+            self.git(
+                &["-C", &root, "add", "--", &file],
+                &format!("{file} is resolved."),
+            );
+        } else {
+            self.notice = Some(format!("{file} still has {} conflicts.", self.hunks.len()));
+        }
+    }
+
+    fn proceed(&mut self) -> ConflictsAction {
+        let Some(operation) = self.operation else {
+            self.notice = Some(String::from(
+                "No merge, rebase, cherry-pick or revert in progress.",
+            ));
+            return ConflictsAction::Stay;
+        };
+        if !self.files.is_empty() {
+            self.notice = Some(String::from("Resolve every file first."));
+            return ConflictsAction::Stay;
+        }
+        if operation.eq(&Operation::Merge) {
+            return ConflictsAction::Form(git::merge_message().unwrap_or_default());
+        }
+        self.git(
+            &["-c", "core.editor=true", operation.command(), "--continue"],
+            &format!("The {} goes on.", operation.command()),
+        );
+        ConflictsAction::Stay
+    }
+
+    pub fn handle(
+        &mut self,
+        rei: &mut Terminal<CrosstermBackend<Stdout>>,
+        key: KeyEvent,
+    ) -> ConflictsAction {
+        self.notice = None;
+        if self.message.take().is_some() {
+            return ConflictsAction::Stay;
+        }
+        if let Some(confirm) = self.abort.take() {
+            match (Confirm::handle(key), self.operation) {
+                (None, _) => self.abort = Some(confirm),
+                (Some(true), Some(operation)) => self.git(
+                    &[operation.command(), "--abort"],
+                    &format!("The {} is aborted.", operation.command()),
+                ),
+                _ => {}
+            }
+            return ConflictsAction::Stay;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return ConflictsAction::Back,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.hunk = 0;
+                self.select(self.selected + 1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.hunk = 0;
+                self.select(self.selected.saturating_sub(1));
+            }
+            KeyCode::Right | KeyCode::Char('l' | 'n') => {
+                self.hunk = (self.hunk + 1).min(self.hunks.len().saturating_sub(1));
+            }
+            KeyCode::Left | KeyCode::Char('h' | 'p') => {
+                self.hunk = self.hunk.saturating_sub(1);
+            }
+            KeyCode::Char('o') => self.choose(Side::Ours),
+            KeyCode::Char('t') => self.choose(Side::Theirs),
+            KeyCode::Char('b') => self.choose(Side::Both),
+            KeyCode::Char('e') => {
+                if let Some(path) = self.path() {
+                    if let Err(e) = external::run(rei, &path.display().to_string()) {
+                        self.message = Some(e);
+                    }
+                    self.select(self.selected);
+                }
+            }
+            KeyCode::Char('a') => self.mark(),
+            KeyCode::Char('c') => return self.proceed(),
+            KeyCode::Char('A') => {
+                if let Some(operation) = self.operation {
+                    self.abort = Some(Confirm::new(&format!(
+                        "Abort the {}? The resolved conflicts will be lost.",
+                        operation.command()
+                    )));
+                }
+            }
+            _ => {}
+        }
+        ConflictsAction::Stay
+    }
+
+    fn render_hunk(&self, f: &mut Frame, app: &App, area: Rect) {
+        let Some(hunk) = self.hunks.get(self.hunk) else {
+            let text = match (self.files.get(self.selected), self.operation) {
+                (Some(file), _) => format!("{file} has no conflict left, a marks it resolved."),
+                (None, Some(operation)) => {
+                    format!("No conflict left, c continues the {}.", operation.command())
+                }
+                (None, None) => String::from("No conflict."),
+            };
+            f.render_widget(Paragraph::new(text), area);
+            return;
+        };
+        let [title, panes] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        f.render_widget(
+            Paragraph::new(format!(
+                "Conflict {}/{}, lines {} to {}",
+                self.hunk + 1,
+                self.hunks.len(),
+                hunk.start + 1,
+                hunk.end + 1
+            )),
+            title,
+        );
+        let mut sides = vec![("Ours", Some(&hunk.ours))];
+        sides.push(("Base", hunk.base.as_ref()));
+        sides.push(("Theirs", Some(&hunk.theirs)));
+        let areas = Layout::vertical([Constraint::Ratio(1, 3); 3]).split(panes);
+        for ((name, lines), area) in sides.into_iter().zip(areas.iter()) {
+            let text = lines.map_or_else(
+                || String::from("Set merge.conflictStyle to diff3 to see the base."),
+                |lines| lines.concat(),
+            );
+            let style = if lines.is_some() {
+                app.theme.text()
+            } else {
+                app.theme.inactive()
+            };
+            f.render_widget(
+                Paragraph::new(text).style(style).block(
+                    Block::default()
+                        .borders(Borders::all())
+                        .border_type(BorderType::Rounded)
+                        .title(format!(" {name} ")),
+                ),
+                *area,
+            );
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        let title = self.operation.map_or_else(
+            || String::from(" Conflicts "),
+            |operation| format!(" Conflicts, {} in progress ", operation.command()),
+        );
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_type(BorderType::Rounded)
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_bottom(
+                " ←→ conflict  o ours  t theirs  b both  e edit  a resolved  c continue  A abort  esc back ",
+            )
+            .title_alignment(Alignment::Center)
+            .style(app.theme.text());
+        let inner = block.inner(f.area());
+        f.render_widget(block, f.area());
+        let [main, notice] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let [files, hunk] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(main);
+        let items: Vec<ListItem> = self
+            .files
+            .iter()
+            .map(|file| ListItem::new(file.as_str()))
+            .collect();
+        let mut state =
+            ListState::default().with_selected((!self.files.is_empty()).then_some(self.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .highlight_style(app.theme.selection())
+                .block(Block::default().borders(Borders::RIGHT).title(" Files ")),
+            files,
+            &mut state,
+        );
+        self.render_hunk(f, app, hunk);
+        if let Some(text) = &self.notice {
+            f.render_widget(Paragraph::new(text.as_str()), notice);
+        }
+        if let Some(confirm) = &self.abort {
+            confirm.render(f, &app.theme);
+        }
+        if let Some(message) = &self.message {
+            render_message(f, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.split_inclusive('\n').map(String::from).collect()
+    }
+
+    #[test]
+    fn merge_style() {
+        let lines = lines("a\n<<<<<<< HEAD\nours\n=======\ntheirs\none more\n>>>>>>> topic\nb\n");
+        let hunks = hunks(&lines);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!((hunks[0].start, hunks[0].end), (1, 6));
+        assert_eq!(hunks[0].ours, ["ours\n"]);
+        assert!(hunks[0].base.is_none());
+        assert_eq!(hunks[0].theirs, ["theirs\n", "one more\n"]);
+    }
+
+    #[test]
+    fn diff3_style() {
+        let lines = lines(
+            "<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> topic\n\
+             <<<<<<< HEAD\n=======\nadded\n>>>>>>> topic",
+        );
+        let hunks = hunks(&lines);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(
+            hunks[0].base.as_deref(),
+            Some(&[String::from("base\n")][..])
+        );
+        assert_eq!((hunks[1].start, hunks[1].end), (7, 10));
+        assert!(hunks[1].ours.is_empty());
+        assert_eq!(hunks[1].theirs, ["added\n"]);
+    }
+
+    #[test]
+    fn unterminated() {
+        assert!(hunks(&lines("<<<<<<< HEAD\nours\n=======\n")).is_empty());
+    }
+
+    #[test]
+    fn resolve_sides() {
+        let text = lines("a\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> topic\nb\n");
+        for (side, expected) in [
+            (Side::Ours, "a\nours\nb\n"),
+            (Side::Theirs, "a\ntheirs\nb\n"),
+            (Side::Both, "a\nours\ntheirs\nb\n"),
+        ] {
+            let mut lines = text.clone();
+            let hunk = &hunks(&lines)[0];
+            resolve(&mut lines, hunk, side);
+            assert_eq!(lines.concat(), expected);
+        }
+    }
+}
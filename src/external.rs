@@ -0,0 +1,124 @@
+use crate::set_text;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{stdout, ErrorKind, Stdout, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use tui_textarea::TextArea;
+
+///
+/// The editor of the user, `$VISUAL` first then `$EDITOR`, `vi` otherwise.
+///
+fn editor() -> String {
+    env::var("VISUAL")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .or_else(|| env::var("EDITOR").ok())
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| String::from("vi"))
+}
+
+///
+/// A new file of the temporary directory holding `text`. It is created
+/// exclusively, a file prepared there by someone else is never written.
+///
+/// # Errors
+///
+/// When no file can be created.
+///
+pub fn temp_file(name: &str, text: &str) -> Result<PathBuf, String> {
+    let dir = env::temp_dir();
+    for n in 0..100 {
+        let path = dir.join(format!("rei-{}-{n}-{name}", process::id()));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                return file
+                    .write_all(text.as_bytes())
+                    .map(|()| path.clone())
+                    .map_err(|e| format!("{}: {e}", path.display()));
+            }
+            Err(e) if e.kind().eq(&ErrorKind::AlreadyExists) => {}
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        }
+    }
+    Err(format!("{}: no free temporary file", dir.display()))
+}
+
+///
+/// `path` as a single word of the shell, in quotes where its own quotes are
+/// escaped.
+///
+pub fn quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', "'\\''"))
+}
+
+///
+/// Give the terminal back while `run` runs, for the programs asking the user
+/// something like an editor or a pinentry.
+///
+pub fn outside<T>(rei: &mut Terminal<CrosstermBackend<Stdout>>, run: impl FnOnce() -> T) -> T {
+    let _ = execute!(stdout(), DisableMouseCapture);
+    ratatui::restore();
+    let result = run();
+    let _ = enable_raw_mode();
+    let _ = execute!(stdout(), EnterAlternateScreen, EnableMouseCapture);
+    let _ = rei.clear();
+    result
+}
+
+///
+/// Leave the terminal to the external editor opened on `path`, then take it back.
+///
+/// # Errors
+///
+/// When the editor can't be started or exit with a failure.
+///
+pub fn run(rei: &mut Terminal<CrosstermBackend<Stdout>>, path: &str) -> Result<(), String> {
+    let editor = editor();
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = outside(rei, || Command::new(program).args(words).arg(path).status());
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("{editor} exited with {status}")),
+        Err(e) => Err(format!("{editor}: {e}")),
+    }
+}
+
+///
+/// Edit the content of the textarea in the external editor, the text is
+/// replaced only when the editor succeed.
+///
+/// # Errors
+///
+/// When the temporary file or the editor fails.
+///
+pub fn edit(
+    rei: &mut Terminal<CrosstermBackend<Stdout>>,
+    textarea: &mut TextArea<'_>,
+    title: &str,
+) -> Result<(), String> {
+    let name: String = title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let path = temp_file(&format!("{name}.md"), &(textarea.lines().join("\n") + "\n"))?;
+    let file = path.display().to_string();
+    let edited = run(rei, &file)
+        .and_then(|()| fs::read_to_string(&path).map_err(|e| format!("{file}: {e}")));
+    let _ = fs::remove_file(&path);
+    let edited = edited?;
+    set_text(textarea, edited.trim_end_matches('\n'));
+    Ok(())
+}
@@ -0,0 +1,363 @@
+use crate::git;
+use crate::prompt::{Prompt, PromptAction};
+use crate::{render_message, App};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::Frame;
+use std::collections::HashMap;
+
+///
+/// A commit which last touched the lines of the staged changes.
+///
+struct Target {
+    hash: String,
+    short: String,
+    subject: String,
+    ///
+    /// The staged lines it touched.
+    ///
+    lines: usize,
+    published: bool,
+}
+
+///
+/// The lines of `HEAD` touched by the staged changes, as `(path, start,
+/// count)`, an addition gives the line before it.
+///
+fn touched(diff: &str) -> Vec<(String, usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut path: Option<&str> = None;
+    for line in diff.lines() {
+        if let Some(old) = line.strip_prefix("--- ") {
+            path = old.strip_prefix("a/");
+            continue;
+        }
+        let (Some(path), Some(header)) = (path, line.strip_prefix("@@ -")) else {
+            continue;
+        };
+        let old = header.split_whitespace().next().unwrap_or_default();
+        let (start, count) = old.split_once(',').unwrap_or((old, "1"));
+        let (Ok(start), Ok(count)) = (start.parse::<usize>(), count.parse::<usize>()) else {
+            continue;
+        };
+        if count.gt(&0) {
+            ranges.push((path.to_string(), start, count));
+        } else if start.gt(&0) {
+            ranges.push((path.to_string(), start, 1));
+        }
+    }
+    ranges
+}
+
+///
+/// The commits to fix, the most touched first.
+///
+fn targets(diff: &str) -> Vec<Target> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (path, start, count) in touched(diff) {
+        for hash in git::blame(&path, start, count) {
+            *counts.entry(hash).or_default() += 1;
+        }
+    }
+    let mut targets: Vec<Target> = counts
+        .into_iter()
+        .filter_map(|(hash, lines)| {
+            let (short, subject) = git::describe(&hash)?;
+            Some(Target {
+                published: git::published(&hash),
+                hash,
+                short,
+                subject,
+                lines,
+            })
+        })
+        .collect();
+    targets.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.subject.cmp(&b.subject)));
+    targets
+}
+
+enum Pending {
+    Squash(Prompt),
+    Autosquash(Prompt),
+}
+
+pub enum FixupAction {
+    Stay,
+    Back,
+}
+
+///
+/// The staged changes committed as a fixup of the commit they correct.
+///
+pub struct Fixup {
+    diff: String,
+    targets: Vec<Target>,
+    selected: usize,
+    scroll: u16,
+    ///
+    /// The last commit fixed, the default base of the autosquash.
+    ///
+    fixed: Option<String>,
+    pending: Option<Pending>,
+    notice: Option<String>,
+    message: Option<String>,
+}
+
+impl Fixup {
+    pub fn new() -> Self {
+        let mut fixup = Self {
+            diff: String::new(),
+            targets: Vec::new(),
+            selected: 0,
+            scroll: 0,
+            fixed: None,
+            pending: None,
+            notice: None,
+            message: None,
+        };
+        fixup.reload();
+        fixup
+    }
+
+    fn reload(&mut self) {
+        self.diff = git::staged(true);
+        self.targets = targets(&git::staged(false));
+        self.selected = self.selected.min(self.targets.len().saturating_sub(1));
+        self.scroll = 0;
+    }
+
+    fn git(&mut self, args: &[&str], done: &str) {
+        match git::notice(args, done) {
+            Ok(notice) => self.notice = Some(notice),
+            Err(output) => self.message = Some(output),
+        }
+        self.reload();
+    }
+
+    fn target(&mut self) -> Option<String> {
+        if self.diff.is_empty() {
+            self.notice = Some(String::from("Nothing is staged."));
+            return None;
+        }
+        self.targets
+            .get(self.selected)
+            .map(|target| target.hash.clone())
+    }
+
+    ///
+    /// Commit the staged changes against `hash`, which becomes the default base
+    /// of the autosquash once git took them.
+    ///
+    fn commit(&mut self, args: &[&str], done: &str, hash: String) {
+        self.git(args, done);
+        if self.message.is_none() {
+            self.fixed = Some(hash);
+        }
+    }
+
+    fn handle_pending(&mut self, mut pending: Pending, key: KeyEvent) {
+        let prompt = match &mut pending {
+            Pending::Squash(prompt) | Pending::Autosquash(prompt) => prompt,
+        };
+        let value = match prompt.handle(key) {
+            PromptAction::Stay => {
+                self.pending = Some(pending);
+                return;
+            }
+            PromptAction::Cancel => return,
+            PromptAction::Submit(value) => value,
+        };
+        match pending {
+            Pending::Squash(_) => {
+                let Some(hash) = self.target() else {
+                    return;
+                };
+                let squash = format!("--squash={hash}");
+                let mut args = vec!["-c", "core.editor=true", "commit", &squash];
+                if !value.is_empty() {
+                    args.extend(["--message", &value]);
+                }
+                self.commit(&args, "Committed the squash.", hash);
+            }
+            Pending::Autosquash(_) if value.is_empty() => {}
+            Pending::Autosquash(_) => {
+                self.git(
+                    &[
+                        "-c",
+                        "sequence.editor=true",
+                        "-c",
+                        "core.editor=true",
+                        "rebase",
+                        "--interactive",
+                        "--autosquash",
+                        "--autostash",
+                        &value,
+                    ],
+                    "The fixups are squashed.",
+                );
+                if let (Some(message), Some(_)) = (self.message.as_mut(), git::operation()) {
+                    message
+                        .push_str("\n\nThe rebase stopped, continue it from the conflicts screen.");
+                }
+            }
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> FixupAction {
+        self.notice = None;
+        if self.message.take().is_some() {
+            return FixupAction::Stay;
+        }
+        if let Some(pending) = self.pending.take() {
+            self.handle_pending(pending, key);
+            return FixupAction::Stay;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return FixupAction::Back,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.targets.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Enter | KeyCode::Char('f') => {
+                if let Some(hash) = self.target() {
+                    let fixup = format!("--fixup={hash}");
+                    self.commit(&["commit", &fixup], "Committed the fixup.", hash);
+                }
+            }
+            KeyCode::Char('s') => {
+                self.pending = self.target().map(|_| {
+                    Pending::Squash(Prompt::new(
+                        "Squash message, empty to keep the commit one",
+                        "",
+                    ))
+                });
+            }
+            KeyCode::Char('a') => {
+                let base = git::upstream()
+                    .or_else(|| self.fixed.as_ref().map(|hash| format!("{hash}~1")))
+                    .unwrap_or_default();
+                self.pending = Some(Pending::Autosquash(Prompt::new("Autosquash onto", &base)));
+            }
+            _ => {}
+        }
+        FixupAction::Stay
+    }
+
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_type(BorderType::Rounded)
+            .title(" Fixup ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(" ⏎ fixup!  s squash!  a autosquash  esc back ")
+            .title_alignment(Alignment::Center)
+            .style(app.theme.text());
+        let inner = block.inner(f.area());
+        f.render_widget(block, f.area());
+        let [list, diff, notice] = Layout::vertical([
+            Constraint::Percentage(30),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        if self.targets.is_empty() {
+            let text = if self.diff.is_empty() {
+                "Nothing is staged."
+            } else {
+                "No commit touched the staged lines."
+            };
+            f.render_widget(Paragraph::new(text).style(app.theme.inactive()), list);
+        } else {
+            let rows: Vec<Row> = self
+                .targets
+                .iter()
+                .map(|target| {
+                    let row = Row::new(vec![
+                        target.short.clone(),
+                        format!("{} lines", target.lines),
+                        target.subject.clone(),
+                        String::from(if target.published { "published" } else { "" }),
+                    ]);
+                    if target.published {
+                        row.style(app.theme.inactive())
+                    } else {
+                        row
+                    }
+                })
+                .collect();
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(9),
+                    Constraint::Length(10),
+                    Constraint::Fill(1),
+                    Constraint::Length(9),
+                ],
+            )
+            .header(Row::new(["Commit", "Touched", "Subject", ""]))
+            .highlight_style(app.theme.selection());
+            let mut state = TableState::default().with_selected(Some(self.selected));
+            f.render_stateful_widget(table, list, &mut state);
+        }
+        f.render_widget(
+            Paragraph::new(self.diff.as_str())
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0))
+                .block(
+                    Block::default()
+                        .borders(Borders::TOP)
+                        .title(" Staged ")
+                        .title_bottom(" pageup pagedown scroll "),
+                ),
+            diff,
+        );
+        if let Some(text) = &self.notice {
+            f.render_widget(Paragraph::new(text.as_str()), notice);
+        }
+        match &self.pending {
+            Some(Pending::Squash(prompt) | Pending::Autosquash(prompt)) => {
+                prompt.render(f, &app.theme);
+            }
+            None => {}
+        }
+        if let Some(message) = &self.message {
+            render_message(f, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touched_lines() {
+        let diff = "diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -3,2 +3,4 @@ fn main() {
+@@ -10 +12 @@
+@@ -20,0 +22,1 @@
+diff --git a/new.rs b/new.rs
+--- /dev/null
++++ b/new.rs
+@@ -0,0 +1,2 @@
+diff --git a/lib.rs b/lib.rs
+--- a/lib.rs
++++ b/lib.rs
+@@ -1,0 +1 @@
+";
+        assert_eq!(
+            touched(diff),
+            vec![
+                (String::from("src/main.rs"), 3, 2),
+                (String::from("src/main.rs"), 10, 1),
+                (String::from("src/main.rs"), 20, 1),
+                (String::from("lib.rs"), 1, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,614 @@
+use crate::complete::{complete, people, Completion, CompletionAction, PEOPLE};
+use crate::keymap::Action;
+use crate::message::Message;
+use crate::mouse::{mouse_action, MouseAction};
+use crate::options::{CommitOptions, OptionsAction, OptionsDialog};
+use crate::overview::{Overview, OverviewAction};
+use crate::profile::Profile;
+use crate::prompt::Confirm;
+use crate::release;
+use crate::requirement::{unmet, Requirements, RequirementsAction};
+use crate::search::{Search, SearchAction};
+use crate::snippet::{SnippetAction, SnippetPicker};
+use crate::spell::{replace_word, word_at, Speller, SuggestionAction, Suggestions};
+use crate::vim::Vim;
+use crate::{
+    activate, external, form_layout, git, inactivate, indicator, message, new_pages,
+    render_message, set_text, App, Page,
+};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders};
+use ratatui::{Frame, Terminal};
+use std::io::Stdout;
+
+///
+/// A popup drawn over the commit form, it receives the keys while open.
+///
+enum Overlay {
+    Overview(Overview),
+    Requirements(Requirements),
+    Snippets(SnippetPicker),
+    Completion(Completion),
+    Options(OptionsDialog),
+    Search(Search),
+    Suggestions(Suggestions),
+    Help,
+    Message(String),
+    ///
+    /// Whether to quit the form and lose its text.
+    ///
+    Quit(Confirm),
+}
+
+///
+/// What the form asks the application after a key.
+///
+pub enum FormAction {
+    Stay,
+    Quit,
+    Preview,
+    ///
+    /// The commit is made and the form emptied, with the git output.
+    ///
+    Committed(String),
+    ///
+    /// The message of a rewording or the notes of a release, once the
+    /// requirements are met.
+    ///
+    Written(String),
+    ///
+    /// Stash the changes, with the title of the form.
+    ///
+    Stash(String),
+}
+
+///
+/// What the form writes.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Writing {
+    Commit,
+    ///
+    /// The message of an existing commit, given back instead of committed.
+    ///
+    Reword,
+    ///
+    /// The notes of a release, given back to be tagged.
+    ///
+    Release,
+}
+
+///
+/// The commit form of a profile and its editing state.
+///
+pub struct Form<'a> {
+    profile: &'a Profile,
+    pages: Vec<Page>,
+    page: usize,
+    witch: usize,
+    overlay: Option<Overlay>,
+    vim: Option<Vim>,
+    zoom: bool,
+    speller: Option<Speller<'a>>,
+    writing: Writing,
+    ///
+    /// The text of the fields when the form was opened or emptied, quitting
+    /// asks first once it changed.
+    ///
+    start: Vec<Vec<String>>,
+    ///
+    /// Whether the fields hold an existing message, whose empty sections are
+    /// left out.
+    ///
+    filled: bool,
+    ///
+    /// The trailers of a rewritten message which belong to no field.
+    ///
+    kept: Vec<String>,
+}
+
+///
+/// The pages of the commit form, filled with the defaults and the committer.
+///
+fn new_form(app: &App) -> [Page; 9] {
+    let mut pages = new_pages();
+    for (p, w, text) in &app.defaults {
+        set_text(&mut pages[*p].areas[*w], text);
+    }
+    let (p, w) = PEOPLE[0];
+    if let Some(identity) = app.identity.as_deref() {
+        if pages[p].areas[w].is_empty() {
+            set_text(&mut pages[p].areas[w], identity);
+        }
+    }
+    pages
+}
+
+fn texts(pages: &[Page]) -> Vec<Vec<String>> {
+    pages
+        .iter()
+        .flat_map(|page| page.areas.iter().map(|area| area.lines().to_vec()))
+        .collect()
+}
+
+impl<'a> Form<'a> {
+    pub fn new(app: &'a App, profile: &'a Profile) -> Self {
+        let pages = Vec::from(new_form(app));
+        Self {
+            profile,
+            start: texts(&pages),
+            pages,
+            page: profile.first(),
+            witch: 0,
+            overlay: None,
+            vim: app.vim.then(Vim::new),
+            zoom: false,
+            speller: app.dictionary.as_ref().map(Speller::new),
+            writing: Writing::Commit,
+            filled: false,
+            kept: Vec::new(),
+        }
+    }
+
+    ///
+    /// The form of an existing commit message.
+    ///
+    pub fn rewording(app: &'a App, profile: &'a Profile, message: &Message) -> Self {
+        let mut form = Self::new(app, profile);
+        form.pages = Vec::from(new_pages());
+        form.fill(message);
+        form.writing = Writing::Reword;
+        form
+    }
+
+    ///
+    /// The form of the notes of a release, on their own pages.
+    ///
+    pub fn releasing(app: &'a App, pages: Vec<Page>) -> Self {
+        let mut form = Self::new(app, &app.release);
+        form.start = texts(&pages);
+        form.pages = pages;
+        form.writing = Writing::Release;
+        form
+    }
+
+    ///
+    /// Empty the form after a commit, the editing preferences are kept.
+    ///
+    fn reset(&mut self, app: &App) {
+        self.pages = Vec::from(new_form(app));
+        self.start = texts(&self.pages);
+        self.kept.clear();
+        self.filled = false;
+        self.page = self.profile.first();
+        self.witch = 0;
+        self.overlay = None;
+    }
+
+    ///
+    /// Fill the fields with those of an existing commit message, its other
+    /// trailers are kept as they are.
+    ///
+    pub fn fill(&mut self, message: &Message) {
+        for (p, w, content) in message {
+            set_text(&mut self.pages[*p].areas[*w], content);
+        }
+        self.kept.clone_from(&message.footer);
+        self.filled = true;
+    }
+
+    ///
+    /// Whether a field changed since the form was opened or emptied.
+    ///
+    fn edited(&self) -> bool {
+        texts(&self.pages).ne(&self.start)
+    }
+
+    ///
+    /// The first field on one line, the title of the commit or the name of
+    /// the tag.
+    ///
+    pub fn title(&self) -> String {
+        self.pages[0].areas[0].lines().join(" ").trim().to_string()
+    }
+
+    ///
+    /// The commit message written by the form, or the message of the tag.
+    ///
+    pub fn message(&self, app: &App) -> String {
+        if self.writing.eq(&Writing::Release) {
+            return release::notes(&self.pages);
+        }
+        message::render(
+            &self.pages,
+            &self.profile.pages,
+            &app.trailers,
+            app.identity.as_deref(),
+            &self.kept,
+            self.filled,
+        )
+    }
+
+    ///
+    /// Ask the commit options, or show the unmet requirements.
+    ///
+    pub fn ask_commit(&mut self, app: &App) {
+        let missing = unmet(&self.pages, &self.profile.pages);
+        self.overlay = Some(if missing.is_empty() {
+            let options = CommitOptions {
+                signoff: app.signoff,
+                sign: git::signs(),
+            };
+            Overlay::Options(OptionsDialog::new(options, git::signing_format()))
+        } else {
+            Overlay::Requirements(Requirements::new(missing))
+        });
+    }
+
+    pub fn render(&mut self, f: &mut Frame, app: &App) {
+        if let Some(speller) = self.speller.as_mut() {
+            if !matches!(self.overlay, Some(Overlay::Search(_))) {
+                let current = &mut self.pages[self.page];
+                for (area, limit) in current.areas.iter_mut().zip(current.limits) {
+                    speller.underline(area, limit, &app.theme);
+                }
+            }
+        }
+        let theme = &app.theme;
+        let mode = self.vim.as_ref().map(Vim::mode);
+        let current = &mut self.pages[self.page];
+        for (i, area) in current.areas.iter_mut().enumerate() {
+            area.set_block(
+                Block::default()
+                    .borders(Borders::all())
+                    .title_alignment(Alignment::Left)
+                    .title(format!(" {} ", current.titles[i])),
+            );
+            area.set_line_number_style(theme.text());
+            area.set_cursor_style(Style::underlined(theme.text()));
+            if let Some(mode) = mode {
+                area.set_cursor_style(mode.cursor_style(theme));
+            }
+            area.set_selection_style(theme.selection());
+            if i.eq(&self.witch) {
+                activate(
+                    area,
+                    current.titles[i],
+                    current.describe[i],
+                    current.limits[i],
+                    theme,
+                    app.accessible,
+                );
+            } else {
+                inactivate(
+                    area,
+                    current.titles[i],
+                    current.describe[i],
+                    current.limits[i],
+                    theme,
+                    app.accessible,
+                );
+            }
+        }
+        if let Some(Overlay::Overview(overview)) = &self.overlay {
+            overview.render(f, &self.pages, self.profile, theme, app.accessible);
+            return;
+        }
+        let current = &self.pages[self.page];
+        let title = if app.accessible {
+            format!(
+                " {} page {}/{}, field {}/4 {}: {} ",
+                current.main_title,
+                self.profile.position(self.page) + 1,
+                self.profile.pages.len(),
+                self.witch + 1,
+                current.titles[self.witch],
+                indicator(&current.areas[self.witch], current.limits[self.witch])
+            )
+        } else {
+            format!(" {} ", current.main_title)
+        };
+        let mut parent_block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_bottom(self.profile.footer(&self.pages, self.page))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::all());
+        if let Some(mode) = mode {
+            parent_block =
+                parent_block.title_bottom(Line::from(format!(" -- {mode} -- ")).left_aligned());
+        }
+
+        let chunks = form_layout(f.area(), self.witch, self.zoom, app.accessible);
+        f.render_widget(parent_block, f.area());
+        for (area, chunk) in current.areas.iter().zip(chunks) {
+            if !chunk.is_empty() {
+                f.render_widget(area, chunk);
+            }
+        }
+        match &self.overlay {
+            Some(Overlay::Requirements(requirements)) => requirements.render(f, theme),
+            Some(Overlay::Snippets(picker)) => picker.render(f, theme),
+            Some(Overlay::Completion(completion)) => completion.render(f, theme),
+            Some(Overlay::Options(options)) => options.render(f, theme),
+            Some(Overlay::Search(search)) => search.render(f, theme),
+            Some(Overlay::Suggestions(suggestions)) => suggestions.render(f, theme),
+            Some(Overlay::Help) => app.keymap.render_help(f, theme),
+            Some(Overlay::Message(message)) => render_message(f, message),
+            Some(Overlay::Quit(confirm)) => confirm.render(f, theme),
+            _ => {}
+        }
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, screen: Rect, app: &App) {
+        if self.overlay.is_some() {
+            return;
+        }
+        let footer = self.profile.footer(&self.pages, self.page);
+        match mouse_action(
+            mouse,
+            screen,
+            &footer,
+            self.witch,
+            self.zoom,
+            app.accessible,
+        ) {
+            MouseAction::Nothing => {}
+            MouseAction::Focus(i) => self.witch = i,
+            MouseAction::NextPage => {
+                self.witch = 0;
+                self.page = self.profile.next(self.page).unwrap_or(self.page);
+            }
+            MouseAction::PreviousPage => {
+                self.witch = 0;
+                self.page = self.profile.previous(self.page).unwrap_or(self.page);
+            }
+            MouseAction::Scroll(i, rows) => self.pages[self.page].areas[i].scroll((rows, 0)),
+        }
+    }
+
+    ///
+    /// Give the key to the open overlay, `None` when there is none.
+    ///
+    #[allow(clippy::too_many_lines)]
+    fn handle_overlay(
+        &mut self,
+        rei: &mut Terminal<CrosstermBackend<Stdout>>,
+        app: &App,
+        key: KeyEvent,
+        action: Option<Action>,
+    ) -> Option<FormAction> {
+        let profile = self.profile;
+        let overlay = self.overlay.as_mut()?;
+        match overlay {
+            Overlay::Help | Overlay::Message(_) => self.overlay = None,
+            Overlay::Quit(_) => match Confirm::handle(key) {
+                Some(true) => return Some(FormAction::Quit),
+                Some(false) => self.overlay = None,
+                None => {}
+            },
+            Overlay::Overview(_) if action.eq(&Some(Action::Overview)) => self.overlay = None,
+            Overlay::Overview(current) => match current.handle(key, profile) {
+                OverviewAction::Stay => {}
+                OverviewAction::Close => self.overlay = None,
+                OverviewAction::Jump(p, w) => {
+                    self.page = p;
+                    self.witch = w;
+                    self.overlay = None;
+                }
+            },
+            Overlay::Snippets(picker) => match picker.handle(key) {
+                SnippetAction::Stay => {}
+                SnippetAction::Close => self.overlay = None,
+                SnippetAction::Insert(text) => {
+                    self.pages[self.page].areas[self.witch].insert_str(text);
+                    self.overlay = None;
+                }
+            },
+            Overlay::Search(search) => match search.handle(key, &mut self.pages, &profile.pages) {
+                SearchAction::Stay => {}
+                SearchAction::Close => self.overlay = None,
+                SearchAction::Focus(p, w) => {
+                    self.page = p;
+                    self.witch = w;
+                }
+            },
+            Overlay::Suggestions(suggestions) => match suggestions.handle(key) {
+                SuggestionAction::Stay => {}
+                SuggestionAction::Close => self.overlay = None,
+                SuggestionAction::Replace(word) => {
+                    replace_word(&mut self.pages[self.page].areas[self.witch], &word);
+                    self.overlay = None;
+                }
+                SuggestionAction::Add(word) => {
+                    self.overlay = self
+                        .speller
+                        .as_mut()
+                        .and_then(|speller| speller.add(&word).err())
+                        .map(Overlay::Message);
+                }
+            },
+            Overlay::Options(options) => match options.handle(key) {
+                OptionsAction::Stay => {}
+                OptionsAction::Close => self.overlay = None,
+                OptionsAction::Commit(options) => {
+                    let message = self.message(app);
+                    let result = if options.sign {
+                        external::outside(rei, || {
+                            git::commit(&message, options.signoff, options.sign)
+                        })
+                    } else {
+                        git::commit(&message, options.signoff, options.sign)
+                    };
+                    match result {
+                        Ok(output) => {
+                            self.reset(app);
+                            return Some(FormAction::Committed(output));
+                        }
+                        Err(output) => {
+                            self.overlay =
+                                Some(Overlay::Message(format!("The commit failed:\n{output}")));
+                        }
+                    }
+                }
+            },
+            Overlay::Completion(completion) => match completion.handle(key) {
+                CompletionAction::Stay => {}
+                CompletionAction::Close => self.overlay = None,
+                CompletionAction::Complete(text) => {
+                    complete(&mut self.pages[self.page].areas[self.witch], &text);
+                    self.overlay = None;
+                }
+            },
+            Overlay::Requirements(requirements) => match requirements.handle(key) {
+                RequirementsAction::Stay => {}
+                RequirementsAction::Close => self.overlay = None,
+                RequirementsAction::Jump(p, w) => {
+                    self.page = p;
+                    self.witch = w;
+                    self.overlay = None;
+                }
+            },
+        }
+        Some(FormAction::Stay)
+    }
+
+    pub fn handle_key(
+        &mut self,
+        rei: &mut Terminal<CrosstermBackend<Stdout>>,
+        app: &App,
+        key: KeyEvent,
+    ) -> FormAction {
+        let action = if self.vim.as_ref().is_some_and(|vim| vim.captures(key)) {
+            None
+        } else {
+            app.keymap.action(key)
+        };
+        if let Some(result) = self.handle_overlay(rei, app, key, action) {
+            return result;
+        }
+        let (page, witch) = (self.page, self.witch);
+        match action {
+            Some(Action::Quit) if self.edited() => {
+                self.overlay = Some(Overlay::Quit(Confirm::new(
+                    "Quit the form? Its text will be lost.",
+                )));
+            }
+            Some(Action::Quit) => return FormAction::Quit,
+            Some(Action::Commit) if self.writing.ne(&Writing::Commit) => {
+                let missing = unmet(&self.pages, &self.profile.pages);
+                if missing.is_empty() {
+                    return FormAction::Written(self.message(app));
+                }
+                self.overlay = Some(Overlay::Requirements(Requirements::new(missing)));
+            }
+            Some(Action::Commit) => self.ask_commit(app),
+            Some(Action::Preview) if self.writing.ne(&Writing::Commit) => {
+                self.overlay = Some(Overlay::Message(self.message(app)));
+            }
+            Some(Action::Preview) => return FormAction::Preview,
+            Some(Action::Stash) => return FormAction::Stash(self.title()),
+            Some(Action::Overview) => {
+                self.overlay = Some(Overlay::Overview(Overview::new(self.profile, page, witch)));
+            }
+            Some(Action::Help) => self.overlay = Some(Overlay::Help),
+            Some(Action::Search) => self.overlay = Some(Overlay::Search(Search::new())),
+            Some(Action::Spell) => {
+                let area = &self.pages[page].areas[witch];
+                self.overlay = Some(match (self.speller.as_mut(), word_at(area)) {
+                    (None, _) => Overlay::Message(String::from(
+                        "No dictionary found, set spell.dictionary in the configuration.",
+                    )),
+                    (Some(_), None) => {
+                        Overlay::Message(String::from("The cursor is not on a word."))
+                    }
+                    (Some(speller), Some((word, _, _))) => {
+                        if speller.check(&word) {
+                            Overlay::Message(format!("\"{word}\" is correct."))
+                        } else {
+                            let suggestions = speller.suggest(&word);
+                            Overlay::Suggestions(Suggestions::new(word, suggestions))
+                        }
+                    }
+                });
+            }
+            Some(Action::Snippets) => {
+                let title = self.pages[page].titles[witch];
+                let picker = SnippetPicker::new(&app.snippets, title);
+                self.overlay = Some(if picker.is_empty() {
+                    Overlay::Message(format!("No snippet for {title}."))
+                } else {
+                    Overlay::Snippets(picker)
+                });
+            }
+            Some(Action::Complete) if PEOPLE.contains(&(page, witch)) => {
+                let completion =
+                    Completion::new(people(&app.trailers), &self.pages[page].areas[witch]);
+                self.overlay = Some(if completion.is_empty() {
+                    Overlay::Message(String::from("Nobody of the history matches this line."))
+                } else {
+                    Overlay::Completion(completion)
+                });
+            }
+            Some(Action::Complete) => {
+                self.overlay = Some(Overlay::Message(format!(
+                    "{} has no completion.",
+                    self.pages[page].titles[witch]
+                )));
+            }
+            Some(Action::Zoom) => self.zoom = !self.zoom,
+            Some(Action::Reflow) => {
+                let current = &mut self.pages[page];
+                match current.limits[witch].max {
+                    Some(width) if current.limits[witch].prose => {
+                        let lines = crate::limit::reflow(current.areas[witch].lines(), width);
+                        set_text(&mut current.areas[witch], &lines.join("\n"));
+                    }
+                    _ => {
+                        self.overlay = Some(Overlay::Message(format!(
+                            "{} holds code, it is never reflowed.",
+                            current.titles[witch]
+                        )));
+                    }
+                }
+            }
+            Some(Action::ExternalEditor) => {
+                let title = self.pages[page].titles[witch];
+                if let Err(e) = external::edit(rei, &mut self.pages[page].areas[witch], title) {
+                    self.overlay = Some(Overlay::Message(e));
+                }
+            }
+            Some(Action::NextPage) => {
+                self.witch = 0;
+                self.page = self.profile.next(page).unwrap_or(page);
+            }
+            Some(Action::PreviousPage) => {
+                self.witch = 0;
+                self.page = self.profile.previous(page).unwrap_or(page);
+            }
+            Some(Action::NextField) => {
+                if witch.lt(&3) {
+                    self.witch += 1;
+                }
+            }
+            Some(Action::PreviousField) => {
+                if witch.gt(&0) {
+                    self.witch -= 1;
+                }
+            }
+            Some(Action::OpenForm) | None => {
+                let area = &mut self.pages[page].areas[witch];
+                if let Some(vim) = self.vim.as_mut() {
+                    vim.handle(key, area);
+                } else {
+                    area.input(key);
+                }
+            }
+        }
+        FormAction::Stay
+    }
+}
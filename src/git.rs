@@ -0,0 +1,609 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+///
+/// Run git in the current directory and give its standard output, `None`
+/// when git fails.
+///
+pub fn output(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .current_dir(".")
+        .output()
+        .ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        None
+    }
+}
+
+///
+/// The root of the working tree.
+///
+pub fn toplevel() -> Option<PathBuf> {
+    output(&["rev-parse", "--show-toplevel"]).map(|path| PathBuf::from(path.trim()))
+}
+
+///
+/// The committer, as `name <email>` from `user.name` and `user.email`.
+///
+pub fn identity() -> Option<String> {
+    let name = output(&["config", "user.name"])?;
+    let email = output(&["config", "user.email"])?;
+    Some(format!("{} <{}>", name.trim(), email.trim()))
+}
+
+///
+/// The authors of the history, most active first, `.mailmap` applied.
+///
+pub fn authors() -> Vec<String> {
+    let mut commits: HashMap<String, usize> = HashMap::new();
+    for author in output(&["log", "--format=%aN <%aE>"])
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+    {
+        *commits.entry(author.to_string()).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = commits.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.into_iter().map(|(author, _)| author).collect()
+}
+
+///
+/// The last `count` commits, one line each.
+///
+pub fn log(count: usize) -> Vec<String> {
+    output(&[
+        "log",
+        &format!("--max-count={count}"),
+        "--format=%h %s (%an, %ar)",
+    ])
+    .unwrap_or_default()
+    .lines()
+    .map(String::from)
+    .collect()
+}
+
+///
+/// The messages of the last `count` commits.
+///
+pub fn messages(count: usize) -> Vec<String> {
+    split(output(&[
+        "log",
+        &format!("--max-count={count}"),
+        "--format=%B%x00",
+    ]))
+}
+
+fn split(messages: Option<String>) -> Vec<String> {
+    messages
+        .unwrap_or_default()
+        .split('\0')
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+///
+/// The messages of the commits after `tag`, the oldest first, or of the
+/// whole history without tag.
+///
+pub fn messages_since(tag: Option<&str>) -> Vec<String> {
+    let range = tag.map_or_else(|| String::from("HEAD"), |tag| format!("{tag}..HEAD"));
+    split(output(&["log", "--reverse", "--format=%B%x00", &range]))
+}
+
+///
+/// The tag reachable from `HEAD` nearest to it.
+///
+pub fn last_tag() -> Option<String> {
+    output(&["describe", "--tags", "--abbrev=0"])
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+}
+
+///
+/// Whether `tag.gpgSign` asks to sign the tags.
+///
+pub fn signs_tags() -> bool {
+    output(&["config", "--type=bool", "tag.gpgSign"]).is_some_and(|value| value.trim().eq("true"))
+}
+
+///
+/// Create the annotated tag `name` on `HEAD`.
+///
+/// # Errors
+///
+/// With the git output when the tag can not be created.
+///
+pub fn tag(name: &str, message: &str, sign: bool) -> Result<String, String> {
+    run(&[
+        "tag",
+        if sign { "--sign" } else { "--annotate" },
+        "--message",
+        message,
+        name,
+    ])
+}
+
+///
+/// Whether `commit.gpgsign` asks to sign the commits.
+///
+pub fn signs() -> bool {
+    output(&["config", "--type=bool", "commit.gpgsign"])
+        .is_some_and(|value| value.trim().eq("true"))
+}
+
+///
+/// The kind of key used to sign, from `gpg.format`.
+///
+pub fn signing_format() -> &'static str {
+    match output(&["config", "gpg.format"]).as_deref().map(str::trim) {
+        Some("ssh") => "SSH",
+        Some("x509") => "X.509",
+        _ => "GPG",
+    }
+}
+
+///
+/// Commit the staged changes with `message`, git output is given back.
+///
+/// # Errors
+///
+/// With the git output when the commit fails.
+///
+pub fn commit(message: &str, signoff: bool, sign: bool) -> Result<String, String> {
+    let mut args = vec!["commit", "-m", message];
+    if signoff {
+        args.push("--signoff");
+    }
+    args.push(if sign { "--gpg-sign" } else { "--no-gpg-sign" });
+    run(&args)
+}
+
+///
+/// The signature status of the last commit, as reported by `%G?`.
+///
+pub fn signature() -> String {
+    let status = output(&["log", "-1", "--format=%G?%x00%GS"]).unwrap_or_default();
+    let (code, signer) = status
+        .trim()
+        .split_once('\0')
+        .unwrap_or((status.trim(), ""));
+    match code {
+        "G" => format!("good signature from {signer}"),
+        "U" => format!("good signature of unknown validity from {signer}"),
+        "X" => format!("good signature from {signer}, expired"),
+        "Y" => format!("good signature from {signer}, by an expired key"),
+        "R" => format!("good signature from {signer}, by a revoked key"),
+        "B" => String::from("bad signature"),
+        "E" => String::from("signature can't be checked, the key is missing"),
+        _ => String::from("not signed"),
+    }
+}
+
+///
+/// The state of the repository shown on the home screen.
+///
+pub struct Summary {
+    pub name: String,
+    pub branch: String,
+    ///
+    /// The commits ahead and behind the upstream, when there is one.
+    ///
+    pub upstream: Option<(usize, usize)>,
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub last: Option<String>,
+}
+
+///
+/// The summary of the repository, `None` outside of one.
+///
+pub fn summary() -> Option<Summary> {
+    let root = toplevel()?;
+    let branch = output(&["branch", "--show-current"])
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty())
+        .or_else(|| {
+            output(&["rev-parse", "--short", "HEAD"])
+                .map(|head| format!("detached at {}", head.trim()))
+        })
+        .unwrap_or_else(|| String::from("no commit yet"));
+    let upstream =
+        output(&["rev-list", "--left-right", "--count", "HEAD...@{upstream}"]).and_then(|counts| {
+            let mut counts = counts.split_whitespace().map(str::parse::<usize>);
+            Some((counts.next()?.ok()?, counts.next()?.ok()?))
+        });
+    let mut summary = Summary {
+        name: root
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().to_string()),
+        branch,
+        upstream,
+        staged: 0,
+        unstaged: 0,
+        untracked: 0,
+        last: output(&["log", "-1", "--format=%s"]).map(|subject| subject.trim().to_string()),
+    };
+    for line in output(&["status", "--porcelain=v1"])
+        .unwrap_or_default()
+        .lines()
+    {
+        let mut states = line.chars();
+        let (index, tree) = (states.next().unwrap_or(' '), states.next().unwrap_or(' '));
+        if index.eq(&'?') {
+            summary.untracked += 1;
+            continue;
+        }
+        if index.ne(&' ') {
+            summary.staged += 1;
+        }
+        if tree.ne(&' ') {
+            summary.unstaged += 1;
+        }
+    }
+    Some(summary)
+}
+
+///
+/// The working tree status, one short line per file.
+///
+pub fn status() -> Vec<String> {
+    output(&["status", "--short", "--branch"])
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+///
+/// Run git and give its output, standard and error merged.
+///
+/// # Errors
+///
+/// With the git output when git fails.
+///
+pub fn run(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .stdin(Stdio::null())
+        .current_dir(".")
+        .output()
+        .map_err(|e| format!("git: {e}"))?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+    .trim()
+    .to_string();
+    if output.status.success() {
+        Ok(text)
+    } else {
+        Err(text)
+    }
+}
+
+///
+/// Run git for the user and give the line to show, the first of its output or
+/// `done` when git says nothing.
+///
+/// # Errors
+///
+/// With the whole git output when git fails.
+///
+pub fn notice(args: &[&str], done: &str) -> Result<String, String> {
+    run(args).map(|output| {
+        output
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .unwrap_or(done)
+            .to_string()
+    })
+}
+
+///
+/// A local or remote-tracking branch with its last commit.
+///
+pub struct Branch {
+    pub name: String,
+    pub remote: bool,
+    pub current: bool,
+    pub upstream: String,
+    pub commit: String,
+    pub date: String,
+    pub subject: String,
+}
+
+///
+/// The local branches then the remote-tracking ones.
+///
+pub fn branches() -> Vec<Branch> {
+    output(&[
+        "for-each-ref",
+        "--format=%(HEAD)%00%(refname)%00%(refname:short)%00%(upstream:short)%00%(objectname:short)%00%(committerdate:relative)%00%(subject)",
+        "refs/heads",
+        "refs/remotes",
+    ])
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| {
+        let fields: Vec<&str> = line.split('\0').collect();
+        let [head, refname, name, upstream, commit, date, subject] = fields[..] else {
+            return None;
+        };
+        if refname.starts_with("refs/remotes/") && refname.ends_with("/HEAD") {
+            return None;
+        }
+        Some(Branch {
+            name: name.to_string(),
+            remote: refname.starts_with("refs/remotes/"),
+            current: head.eq("*"),
+            upstream: upstream.to_string(),
+            commit: commit.to_string(),
+            date: date.to_string(),
+            subject: subject.to_string(),
+        })
+    })
+    .collect()
+}
+
+///
+/// Whether the local branch `name` is merged in the current one.
+///
+pub fn merged(name: &str) -> bool {
+    output(&["branch", "--merged", "HEAD", "--format=%(refname:short)"])
+        .unwrap_or_default()
+        .lines()
+        .any(|branch| branch.eq(name))
+}
+
+///
+/// `name` once git takes it as a branch name, so that a name typed by the user
+/// can not pass for an option.
+///
+/// # Errors
+///
+/// With the git output when `name` is not a valid branch name.
+///
+pub fn branch_name(name: &str) -> Result<String, String> {
+    run(&["check-ref-format", "--branch", name])
+}
+
+///
+/// A stash entry, `name` is its `stash@{n}` reference.
+///
+pub struct Stash {
+    pub name: String,
+    pub date: String,
+    pub subject: String,
+}
+
+pub fn stashes() -> Vec<Stash> {
+    output(&["stash", "list", "--format=%gd%x00%cr%x00%gs"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            Some(Stash {
+                name: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+///
+/// The changes kept by the stash `name`, untracked files included.
+///
+pub fn stash_diff(name: &str) -> String {
+    output(&["stash", "show", "--patch", "--include-untracked", name])
+        .or_else(|| output(&["stash", "show", "--patch", name]))
+        .unwrap_or_default()
+}
+
+///
+/// The operation stopped on conflicts.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+impl Operation {
+    ///
+    /// The git command of the operation, also its name for the user.
+    ///
+    pub const fn command(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+            Self::CherryPick => "cherry-pick",
+            Self::Revert => "revert",
+        }
+    }
+}
+
+///
+/// The path of `name` in the git directory, which may not exist.
+///
+fn git_path(name: &str) -> Option<PathBuf> {
+    output(&["rev-parse", "--path-format=absolute", "--git-path", name])
+        .map(|path| PathBuf::from(path.trim()))
+}
+
+///
+/// The operation in progress, if any.
+///
+pub fn operation() -> Option<Operation> {
+    [
+        ("rebase-merge", Operation::Rebase),
+        ("rebase-apply", Operation::Rebase),
+        ("MERGE_HEAD", Operation::Merge),
+        ("CHERRY_PICK_HEAD", Operation::CherryPick),
+        ("REVERT_HEAD", Operation::Revert),
+    ]
+    .into_iter()
+    .find(|(name, _)| git_path(name).is_some_and(|path| path.exists()))
+    .map(|(_, operation)| operation)
+}
+
+///
+/// The message prepared by git for the merge, without its comments.
+///
+pub fn merge_message() -> Option<String> {
+    let text = std::fs::read_to_string(git_path("MERGE_MSG")?).ok()?;
+    Some(
+        text.lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect::<Vec<&str>>()
+            .join("\n")
+            .trim()
+            .to_string(),
+    )
+}
+
+///
+/// The unmerged files, relative to the root of the repository.
+///
+pub fn conflicts() -> Vec<String> {
+    output(&["diff", "--name-only", "--diff-filter=U"])
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+///
+/// The upstream of the current branch, like `origin/main`.
+///
+pub fn upstream() -> Option<String> {
+    output(&["rev-parse", "--abbrev-ref", "@{upstream}"])
+        .map(|upstream| upstream.trim().to_string())
+        .filter(|upstream| !upstream.is_empty())
+}
+
+///
+/// A commit given to the rebase.
+///
+pub struct Commit {
+    pub hash: String,
+    pub short: String,
+    pub message: String,
+}
+
+///
+/// The commits of `HEAD` after `base`, the oldest first, without the merges
+/// a rebase leaves out.
+///
+pub fn commits(base: &str) -> Option<Vec<Commit>> {
+    let range = format!("{base}..HEAD");
+    Some(
+        output(&[
+            "log",
+            "--reverse",
+            "--no-merges",
+            "--format=%H%x00%h%x00%B%x01",
+            &range,
+        ])?
+        .split('\u{1}')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\0');
+            Some(Commit {
+                hash: fields.next()?.to_string(),
+                short: fields.next()?.to_string(),
+                message: fields.next()?.trim().to_string(),
+            })
+        })
+        .collect(),
+    )
+}
+
+///
+/// The staged changes, without context lines when `context` is false.
+///
+pub fn staged(context: bool) -> String {
+    let unified = if context {
+        "--unified=3"
+    } else {
+        "--unified=0"
+    };
+    output(&[
+        "diff",
+        "--cached",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        unified,
+    ])
+    .unwrap_or_default()
+}
+
+///
+/// The commits which last touched the `count` lines from `start` of `path`
+/// in `HEAD`, one per line, `path` is relative to the root of the repository.
+///
+pub fn blame(path: &str, start: usize, count: usize) -> Vec<String> {
+    let Some(root) = toplevel() else {
+        return Vec::new();
+    };
+    let root = root.display().to_string();
+    let range = format!("{start},+{count}");
+    output(&[
+        "-C",
+        &root,
+        "blame",
+        "--porcelain",
+        "-L",
+        &range,
+        "HEAD",
+        "--",
+        path,
+    ])
+    .unwrap_or_default()
+    .lines()
+    .filter(|line| !line.starts_with('\t'))
+    .filter_map(|line| {
+        let mut words = line.split_whitespace();
+        let hash = words.next()?;
+        (hash.len().eq(&40) && hash.chars().all(|c| c.is_ascii_hexdigit()) && words.count().ge(&2))
+            .then(|| hash.to_string())
+    })
+    .collect()
+}
+
+///
+/// The short hash and the subject of `hash`.
+///
+pub fn describe(hash: &str) -> Option<(String, String)> {
+    let text = output(&["show", "--no-patch", "--format=%h%x00%s", hash])?;
+    let (short, subject) = text.trim().split_once('\0')?;
+    Some((short.to_string(), subject.to_string()))
+}
+
+///
+/// Whether `hash` is already in the upstream of the current branch.
+///
+pub fn published(hash: &str) -> bool {
+    Command::new("git")
+        .args(["merge-base", "--is-ancestor", hash, "@{upstream}"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .stdout(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
@@ -0,0 +1,204 @@
+use crate::git::{self, Summary};
+use crate::keymap::Action;
+use crate::profile::{ProfileAction, ProfilePicker};
+use crate::App;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Padding, Paragraph};
+use ratatui::Frame;
+
+///
+/// An entry of the home menu.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Item {
+    Commit,
+    Status,
+    Log,
+    Branches,
+    Stash,
+    Release,
+    Conflicts,
+    Rebase,
+    Fixup,
+}
+
+impl Item {
+    const ALL: [Self; 9] = [
+        Self::Commit,
+        Self::Status,
+        Self::Log,
+        Self::Branches,
+        Self::Stash,
+        Self::Release,
+        Self::Conflicts,
+        Self::Rebase,
+        Self::Fixup,
+    ];
+
+    const fn shortcut(self) -> char {
+        match self {
+            Self::Commit => 'c',
+            Self::Status => 's',
+            Self::Log => 'l',
+            Self::Branches => 'b',
+            Self::Stash => 'z',
+            Self::Release => 'r',
+            Self::Conflicts => 'x',
+            Self::Rebase => 'i',
+            Self::Fixup => 'f',
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Commit => "Commit",
+            Self::Status => "Status",
+            Self::Log => "Log",
+            Self::Branches => "Branches",
+            Self::Stash => "Stash",
+            Self::Release => "Release",
+            Self::Conflicts => "Conflicts",
+            Self::Rebase => "Rebase",
+            Self::Fixup => "Fixup",
+        }
+    }
+}
+
+pub enum HomeAction {
+    Stay,
+    Quit,
+    Form(usize),
+    Open(Item),
+}
+
+///
+/// The screen shown when rei starts, the state of the repository and the
+/// menu of the other screens.
+///
+pub struct Home {
+    summary: Option<Summary>,
+    selected: usize,
+    picker: Option<ProfilePicker>,
+    help: bool,
+}
+
+impl Home {
+    pub fn new() -> Self {
+        Self {
+            summary: git::summary(),
+            selected: 0,
+            picker: None,
+            help: false,
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let Some(summary) = &self.summary else {
+            return vec![Line::from("Not in a git repository.")];
+        };
+        let upstream = match summary.upstream {
+            Some((0, 0)) => String::from("up to date with its upstream"),
+            Some((ahead, behind)) => format!("{ahead} ahead, {behind} behind its upstream"),
+            None => String::from("no upstream"),
+        };
+        vec![
+            Line::from(vec![
+                Span::from(summary.name.clone()).bold(),
+                Span::from(format!(" on {}", summary.branch)),
+            ]),
+            Line::from(upstream),
+            Line::from(format!(
+                "{} staged, {} unstaged, {} untracked",
+                summary.staged, summary.unstaged, summary.untracked
+            )),
+            Line::from(format!(
+                "Last commit: {}",
+                summary.last.as_deref().unwrap_or("none")
+            )),
+        ]
+    }
+
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1))
+            .title(" rei ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(format!(
+                " ⏎ open  {} help  {} quit ",
+                app.keymap.keys(Action::Help),
+                app.keymap.keys(Action::Quit)
+            ))
+            .title_alignment(Alignment::Center)
+            .style(app.theme.text());
+        let inner = block.inner(f.area());
+        f.render_widget(block, f.area());
+        let [summary, menu] =
+            Layout::vertical([Constraint::Length(5), Constraint::Min(0)]).areas(inner);
+        f.render_widget(Paragraph::new(self.lines()), summary);
+        let items: Vec<ListItem> = Item::ALL
+            .iter()
+            .map(|item| ListItem::new(format!("[{}] {}", item.shortcut(), item.label())))
+            .collect();
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        f.render_stateful_widget(
+            List::new(items).highlight_style(app.theme.selection()),
+            menu,
+            &mut state,
+        );
+        if let Some(picker) = &self.picker {
+            picker.render(f, &app.profiles, &app.theme);
+        }
+        if self.help {
+            app.keymap.render_help(f, &app.theme);
+        }
+    }
+
+    fn open(&mut self, item: Item, app: &App) -> HomeAction {
+        if item.eq(&Item::Commit) {
+            self.picker = Some(ProfilePicker::new(app.profile));
+            return HomeAction::Stay;
+        }
+        HomeAction::Open(item)
+    }
+
+    pub fn handle(&mut self, app: &App, key: KeyEvent) -> HomeAction {
+        if self.help {
+            self.help = false;
+            return HomeAction::Stay;
+        }
+        if let Some(picker) = self.picker.as_mut() {
+            match picker.handle(key, &app.profiles) {
+                ProfileAction::Stay => {}
+                ProfileAction::Close => self.picker = None,
+                ProfileAction::Open(profile) => return HomeAction::Form(profile),
+            }
+            return HomeAction::Stay;
+        }
+        match app.keymap.action(key) {
+            Some(Action::Quit) => return HomeAction::Quit,
+            Some(Action::Help) => self.help = true,
+            Some(Action::OpenForm) => return self.open(Item::Commit, app),
+            _ => match key.code {
+                KeyCode::Enter => return self.open(Item::ALL[self.selected], app),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(Item::ALL.len() - 1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                KeyCode::Char(c) => {
+                    if let Some(item) = Item::ALL.iter().find(|item| item.shortcut().eq(&c)) {
+                        return self.open(*item, app);
+                    }
+                }
+                _ => {}
+            },
+        }
+        HomeAction::Stay
+    }
+}
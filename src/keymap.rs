@@ -0,0 +1,435 @@
+use crate::centered;
+use crate::config::Keys;
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint};
+use ratatui::style::Stylize;
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Row, Table};
+use ratatui::Frame;
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    OpenForm,
+    Commit,
+    NextField,
+    PreviousField,
+    NextPage,
+    PreviousPage,
+    Overview,
+    ExternalEditor,
+    Zoom,
+    Reflow,
+    Snippets,
+    Complete,
+    Search,
+    Spell,
+    Preview,
+    Stash,
+    Help,
+}
+
+impl Action {
+    pub const ALL: [Self; 18] = [
+        Self::OpenForm,
+        Self::Commit,
+        Self::NextField,
+        Self::PreviousField,
+        Self::NextPage,
+        Self::PreviousPage,
+        Self::Overview,
+        Self::ExternalEditor,
+        Self::Zoom,
+        Self::Reflow,
+        Self::Snippets,
+        Self::Complete,
+        Self::Search,
+        Self::Spell,
+        Self::Preview,
+        Self::Stash,
+        Self::Help,
+        Self::Quit,
+    ];
+
+    pub const fn describe(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit the current screen",
+            Self::OpenForm => "Open the commit form",
+            Self::Commit => "Commit with the form content",
+            Self::NextField => "Focus the next field",
+            Self::PreviousField => "Focus the previous field",
+            Self::NextPage => "Go to the next page",
+            Self::PreviousPage => "Go to the previous page",
+            Self::Overview => "Show the overview of all fields",
+            Self::ExternalEditor => "Edit the field in $VISUAL or $EDITOR",
+            Self::Zoom => "Toggle the full screen view of the field",
+            Self::Reflow => "Wrap the prose of the field to its line length",
+            Self::Snippets => "Insert a snippet at the cursor",
+            Self::Complete => "Complete the line with a person of the history",
+            Self::Search => "Search and replace in every field",
+            Self::Spell => "Suggest the spelling of the word under the cursor",
+            Self::Preview => "Show the commit message",
+            Self::Stash => "Stash the changes with the title as message",
+            Self::Help => "Show the key bindings",
+        }
+    }
+}
+
+#[derive(Deserialize, Copy, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    #[default]
+    Default,
+    Vim,
+    Emacs,
+}
+
+impl Preset {
+    const fn bindings(self) -> &'static [(Action, &'static [&'static str])] {
+        match self {
+            Self::Default => &[
+                (Action::Quit, &["esc"]),
+                (Action::OpenForm, &["f2"]),
+                (Action::Commit, &["f6"]),
+                (Action::NextField, &["f7"]),
+                (Action::PreviousField, &["f5"]),
+                (Action::NextPage, &["pagedown"]),
+                (Action::PreviousPage, &["pageup"]),
+                (Action::Overview, &["f3"]),
+                (Action::ExternalEditor, &["f4"]),
+                (Action::Zoom, &["f8"]),
+                (Action::Reflow, &["f9"]),
+                (Action::Snippets, &["f10"]),
+                (Action::Complete, &["ctrl-space"]),
+                (Action::Search, &["ctrl-f"]),
+                (Action::Spell, &["f12"]),
+                (Action::Preview, &["f11"]),
+                (Action::Stash, &["alt-t"]),
+                (Action::Help, &["f1"]),
+            ],
+            Self::Vim => &[
+                (Action::Quit, &["alt-q"]),
+                (Action::OpenForm, &["f2"]),
+                (Action::Commit, &["alt-w"]),
+                (Action::NextField, &["alt-j"]),
+                (Action::PreviousField, &["alt-k"]),
+                (Action::NextPage, &["alt-l"]),
+                (Action::PreviousPage, &["alt-h"]),
+                (Action::Overview, &["alt-o"]),
+                (Action::ExternalEditor, &["f4", "alt-e"]),
+                (Action::Zoom, &["f8", "alt-z"]),
+                (Action::Reflow, &["f9", "alt-g"]),
+                (Action::Snippets, &["f10", "alt-s"]),
+                (Action::Complete, &["ctrl-space"]),
+                (Action::Search, &["ctrl-f"]),
+                (Action::Spell, &["f12", "alt-="]),
+                (Action::Preview, &["f11", "alt-p"]),
+                (Action::Stash, &["alt-t"]),
+                (Action::Help, &["f1", "alt-?"]),
+            ],
+            Self::Emacs => &[
+                (Action::Quit, &["ctrl-q"]),
+                (Action::OpenForm, &["f2"]),
+                (Action::Commit, &["ctrl-s"]),
+                (Action::NextField, &["ctrl-o"]),
+                (Action::PreviousField, &["alt-o"]),
+                (Action::NextPage, &["alt-}"]),
+                (Action::PreviousPage, &["alt-{"]),
+                (Action::Overview, &["ctrl-t"]),
+                (Action::ExternalEditor, &["f4", "alt-e"]),
+                (Action::Zoom, &["f8", "alt-z"]),
+                (Action::Reflow, &["f9", "alt-q"]),
+                (Action::Snippets, &["f10", "alt-s"]),
+                (Action::Complete, &["ctrl-space"]),
+                (Action::Search, &["alt-%"]),
+                (Action::Spell, &["f12", "alt-$"]),
+                (Action::Preview, &["f11", "alt-p"]),
+                (Action::Stash, &["alt-t"]),
+                (Action::Help, &["f1", "ctrl-l"]),
+            ],
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    ///
+    /// Characters already carry the shift, so it is ignored to compare them.
+    ///
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = if let KeyCode::Char(_) = code {
+            modifiers.difference(KeyModifiers::SHIFT)
+        } else {
+            modifiers
+        };
+        Self {
+            code,
+            modifiers: modifiers
+                .intersection(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT),
+        }
+    }
+}
+
+impl From<KeyEvent> for KeyBinding {
+    fn from(key: KeyEvent) -> Self {
+        Self::normalized(key.code, key.modifiers)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s.trim();
+        loop {
+            let lower = rest.to_lowercase();
+            if rest.chars().count().gt(&1) && lower.starts_with("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[5..];
+            } else if rest.chars().count().gt(&1) && lower.starts_with("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[4..];
+            } else if rest.chars().count().gt(&1) && lower.starts_with("shift-") {
+                modifiers |= KeyModifiers::SHIFT;
+                rest = &rest[6..];
+            } else {
+                break;
+            }
+        }
+        let code = match rest.to_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            lower => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => lower
+                        .strip_prefix('f')
+                        .and_then(|n| n.parse::<u8>().ok())
+                        .filter(|n| (1..=12).contains(n))
+                        .map(KeyCode::F)
+                        .ok_or_else(|| format!("unknown key `{s}`"))?,
+                }
+            }
+        };
+        Ok(Self::normalized(code, modifiers))
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+pub struct KeyMap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl KeyMap {
+    ///
+    /// Build the keymap of the configured preset, the user bindings replace
+    /// the preset ones for the same action and win over the other actions.
+    ///
+    /// # Errors
+    ///
+    /// On a key the parser does not understand.
+    ///
+    pub fn new(keys: &Keys) -> Result<Self, String> {
+        let mut bindings: Vec<(KeyBinding, Action)> = Vec::new();
+        let mut defaults: Vec<(KeyBinding, Action)> = Vec::new();
+        for (action, preset) in keys.preset.bindings() {
+            if let Some(user) = keys.bindings.get(action) {
+                for key in user.keys() {
+                    bindings.push((key.parse()?, *action));
+                }
+            } else {
+                for key in *preset {
+                    defaults.push((key.parse()?, *action));
+                }
+            }
+        }
+        bindings.append(&mut defaults);
+        Ok(Self { bindings })
+    }
+
+    pub fn action(&self, key: KeyEvent) -> Option<Action> {
+        let pressed = KeyBinding::from(key);
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.eq(&pressed))
+            .map(|(_, action)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| a.eq(&action))
+            .map(|(binding, _)| binding.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn render_help(&self, f: &mut Frame, theme: &Theme) {
+        let rows: Vec<Row> = Action::ALL
+            .iter()
+            .map(|action| Row::new([self.keys(*action), action.describe().to_string()]))
+            .collect();
+        let area = centered(f.area(), 70, u16::try_from(rows.len()).unwrap_or(0) + 4);
+        let table = Table::new(rows, [Constraint::Length(20), Constraint::Fill(1)])
+            .header(Row::new(["Keys", "Action"]).style(theme.text().bold()))
+            .style(theme.text())
+            .block(
+                Block::default()
+                    .borders(Borders::all())
+                    .border_type(BorderType::Rounded)
+                    .padding(Padding::horizontal(1))
+                    .title(" Key bindings ")
+                    .title_alignment(Alignment::Center),
+            );
+        f.render_widget(Clear, area);
+        f.render_widget(table, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeyList;
+    use std::collections::HashMap;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn parsed(s: &str) -> KeyBinding {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_keys() {
+        let cases = [
+            ("esc", KeyCode::Esc, KeyModifiers::NONE),
+            ("Ctrl-Space", KeyCode::Char(' '), KeyModifiers::CONTROL),
+            ("alt-j", KeyCode::Char('j'), KeyModifiers::ALT),
+            (
+                "ctrl-alt-x",
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT,
+            ),
+            ("shift-tab", KeyCode::Tab, KeyModifiers::SHIFT),
+            ("F12", KeyCode::F(12), KeyModifiers::NONE),
+            ("pagedown", KeyCode::PageDown, KeyModifiers::NONE),
+            ("-", KeyCode::Char('-'), KeyModifiers::NONE),
+            ("alt--", KeyCode::Char('-'), KeyModifiers::ALT),
+            ("shift-A", KeyCode::Char('A'), KeyModifiers::NONE),
+        ];
+        for (s, code, modifiers) in cases {
+            assert!(parsed(s).eq(&KeyBinding::from(key(code, modifiers))), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        for s in ["f13", "f0", "ctrl-", "hyper-a", "enterr", ""] {
+            assert!(s.parse::<KeyBinding>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(parsed("ctrl-alt-f").to_string(), "Ctrl-Alt-f");
+        assert_eq!(parsed("ctrl-space").to_string(), "Ctrl-Space");
+        assert_eq!(parsed("f5").to_string(), "F5");
+    }
+
+    #[test]
+    fn presets() {
+        for preset in [Preset::Default, Preset::Vim, Preset::Emacs] {
+            let bindings = preset.bindings();
+            assert!(Action::ALL
+                .iter()
+                .all(|action| bindings.iter().any(|(a, _)| a.eq(action))));
+            let keys = Keys {
+                preset,
+                bindings: HashMap::new(),
+            };
+            assert!(KeyMap::new(&keys).is_ok());
+        }
+    }
+
+    #[test]
+    fn user_bindings_win() {
+        let mut bindings = HashMap::new();
+        bindings.insert(Action::Commit, KeyList::One(String::from("f7")));
+        bindings.insert(
+            Action::Help,
+            KeyList::Many(vec![String::from("ctrl-h"), String::from("f1")]),
+        );
+        let keymap = KeyMap::new(&Keys {
+            preset: Preset::Default,
+            bindings,
+        })
+        .unwrap();
+        let f = |n| key(KeyCode::F(n), KeyModifiers::NONE);
+        assert!(keymap.action(f(7)).is_some_and(|a| a.eq(&Action::Commit)));
+        assert!(keymap.action(f(6)).is_none());
+        assert!(keymap.action(f(1)).is_some_and(|a| a.eq(&Action::Help)));
+        assert!(keymap
+            .action(key(KeyCode::Char('h'), KeyModifiers::CONTROL))
+            .is_some_and(|a| a.eq(&Action::Help)));
+        assert_eq!(keymap.keys(Action::NextField), "F7");
+        assert_eq!(keymap.keys(Action::Help), "Ctrl-h, F1");
+    }
+
+    #[test]
+    fn bad_user_binding() {
+        let mut bindings = HashMap::new();
+        bindings.insert(Action::Quit, KeyList::One(String::from("ctrl-nope")));
+        assert!(KeyMap::new(&Keys {
+            preset: Preset::Vim,
+            bindings,
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::theme::Length;
+
+const MAX_LINE_LENGTH: usize = 72;
+
+///
+/// The line length rules of a field.
+///
+/// Lines past `warn` are shown as long, lines past `max` as too long, code
+/// fields have no limit and are never reflowed.
+///
+#[derive(Copy, Clone)]
+pub struct Limit {
+    pub warn: Option<usize>,
+    pub max: Option<usize>,
+    pub prose: bool,
+}
+
+pub const SUBJECT: Limit = Limit {
+    warn: Some(50),
+    max: Some(MAX_LINE_LENGTH),
+    prose: true,
+};
+
+pub const BODY: Limit = Limit {
+    warn: Some(MAX_LINE_LENGTH),
+    max: Some(MAX_LINE_LENGTH),
+    prose: true,
+};
+
+pub const CODE: Limit = Limit {
+    warn: None,
+    max: None,
+    prose: false,
+};
+
+impl Limit {
+    pub fn exceeded(self, length: usize) -> bool {
+        self.max.is_some_and(|max| length.gt(&max))
+    }
+
+    pub fn length(self, length: usize) -> Length {
+        let Some(warn) = self.warn else {
+            return if length.eq(&0) {
+                Length::Empty
+            } else {
+                Length::Short
+            };
+        };
+        if self.exceeded(length) {
+            Length::TooLong
+        } else if length.gt(&warn) {
+            Length::Long
+        } else if length.ge(&(warn * 7 / 10)) {
+            Length::Medium
+        } else if length.ge(&(warn * 4 / 10)) {
+            Length::Short
+        } else {
+            Length::Empty
+        }
+    }
+}
+
+pub fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~")
+}
+
+///
+/// The marker of a list item with its trailing space, like `- ` or `12. `.
+///
+fn list_marker(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    for bullet in ["- ", "* ", "+ "] {
+        if trimmed.starts_with(bullet) {
+            return Some(&line[..indent + bullet.len()]);
+        }
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits.gt(&0) && trimmed[digits..].starts_with(". ") {
+        return Some(&line[..indent + digits + 2]);
+    }
+    None
+}
+
+fn fill(words: &[&str], first: &str, next: &str, width: usize, lines: &mut Vec<String>) {
+    let mut line = first.to_string();
+    let mut empty = true;
+    for word in words {
+        if !empty && (line.chars().count() + 1 + word.chars().count()).gt(&width) {
+            lines.push(line);
+            line = next.to_string();
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+        }
+        line.push_str(word);
+        empty = false;
+    }
+    lines.push(line);
+}
+
+///
+/// Hard wrap the prose at `width`, fenced and indented code, blank lines and
+/// list items are kept.
+///
+pub fn reflow(text: &[String], width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut fenced = false;
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut prefix: (String, String) = (String::new(), String::new());
+    let flush = |paragraph: &mut Vec<&str>, prefix: &(String, String), lines: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            fill(paragraph, &prefix.0, &prefix.1, width, lines);
+            paragraph.clear();
+        }
+    };
+    for line in text {
+        if fenced || is_fence(line) {
+            flush(&mut paragraph, &prefix, &mut lines);
+            if is_fence(line) {
+                fenced = !fenced;
+            }
+            lines.push(line.clone());
+        } else if line.trim().is_empty() || line.starts_with("    ") || line.starts_with('\t') {
+            flush(&mut paragraph, &prefix, &mut lines);
+            lines.push(line.clone());
+        } else if let Some(marker) = list_marker(line) {
+            flush(&mut paragraph, &prefix, &mut lines);
+            prefix = (marker.to_string(), " ".repeat(marker.chars().count()));
+            paragraph.extend(line[marker.len()..].split_whitespace());
+        } else {
+            if paragraph.is_empty() {
+                prefix = (String::new(), String::new());
+            }
+            paragraph.extend(line.split_whitespace());
+        }
+    }
+    flush(&mut paragraph, &prefix, &mut lines);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn reflow_prose() {
+        assert_eq!(
+            reflow(&lines("one two three\nfour five"), 9),
+            lines("one two\nthree\nfour five")
+        );
+    }
+
+    #[test]
+    fn reflow_lists() {
+        assert_eq!(
+            reflow(&lines("- one two three\n12. four five six"), 10),
+            lines("- one two\n  three\n12. four\n    five\n    six")
+        );
+    }
+
+    #[test]
+    fn reflow_keeps_code() {
+        let text = lines("```\na very long line of code\n```\n\n    indented code line\nshort");
+        assert_eq!(reflow(&text, 10), text);
+    }
+
+    #[test]
+    fn reflow_long_word() {
+        assert_eq!(
+            reflow(&lines("a unbreakable-word b"), 5),
+            lines("a\nunbreakable-word\nb")
+        );
+    }
+}
@@ -1,459 +1,333 @@
 #![allow(clippy::too_many_lines)]
 #![allow(clippy::multiple_crate_versions)]
 
-use crate::PageIndex::{Page0, Page1, Page2, Page3, Page4, Page5, Page6, Page7, Page8};
-use crossterm::event::{self, Event, KeyCode};
-use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::style::{Color, Style, Stylize};
-use ratatui::widgets::{Block, BorderType, Borders, Padding};
-use ratatui::{CompletedFrame, Terminal};
-use serde::Serialize;
-use std::fmt::{Display, Formatter};
-use std::io::Stdout;
-use std::process::{Command, Stdio};
+mod branch;
+mod complete;
+mod config;
+mod conflict;
+mod external;
+mod fixup;
+mod form;
+mod git;
+mod home;
+mod keymap;
+mod limit;
+mod message;
+mod mouse;
+mod options;
+mod overview;
+mod profile;
+mod prompt;
+mod rebase;
+mod release;
+mod requirement;
+mod screen;
+mod search;
+mod snippet;
+mod spell;
+mod stash;
+mod theme;
+mod vim;
+
+use crate::config::{Config, EditorMode};
+use crate::keymap::KeyMap;
+use crate::limit::{Limit, BODY, CODE, SUBJECT};
+use crate::message::Trailers;
+use crate::profile::Profile;
+use crate::requirement::Requirement;
+use crate::requirement::Requirement::{Optional, Required, When};
+use crate::snippet::Snippet;
+use crate::spell::Dictionary;
+use crate::theme::{Length, Theme};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap};
+use ratatui::Frame;
+use std::io::stdout;
 use tui_textarea::TextArea;
 
-const COMMIT_TEMPLATE :&str = "%title%
-
-%description%
-
-Steps
-
-```bash
-%steps%
-```
-
-Behavior
-
-%expected_description%
-
-Before
-
-%system_before%
-
-After
-
-%system_after%
-
-Expectation
-
-%expectation%
-
-Examples
-
-%samples%
-
-Vulnerabilities
-
-%vulnerabilities%
-
-Quality
-
-%qualities%
-
-Conformity
-
-%conformity%
-
-Risk
-
-%risk%
-
-Added
-
-%tests_added%
-
-Updated
-
-%tests_updated%
-
-Deleted
-
-%test_deleted%
-
-Platforms
-
-%tested_platforms%
-
-Breaking
-
-%breaking_changes%
-
-Dependencies
-
-%dependencies%
-
-Rollback
-
-%rollbacks%
-
-Up
-
-```sql
-%db_up%
-```
-
-Down
-
-```sql
-%db_down%
-```
-
-Changes
-
-%db_changes%
-
-Why
-
-%why_db_changes%
-
-Authors
-
-%authors%
-
-Testers
-
-%testers%
-
-Comments
-
-%comments%
-
-Notes
-
-%notes%
-
-Head
-
-%news_headline%
-
-Workflow
-
-%workflow%
-
-Samples
-
-%workflows_samples%
-
-Considerations
-
-%technical_considerations%
-
-Next
-
-%next_description%
-
-Motivation
-
-%next_motivation%
-
-Why
-
-%next_reasons%
-
-Links
-
-%next_links%
-
-";
-
-#[derive(Serialize, Default)]
-pub struct Commit {
-    pub title: String,
-    pub description: String,
-    pub steps: String,
-    pub expected_description: String,
-    pub system_before: String,
-    pub system_after: String,
-    pub expectation: String,
-    pub samples: String,
-    pub vulnerabilities: String,
-    pub qualities: String,
-    pub conforms: String,
-    pub risks: String,
-    pub tests_added: String,
-    pub tests_updated: String,
-    pub tests_deleted: String,
-    pub platforms: String,
-    pub breaking_changes: String,
-    pub dependencies: String,
-    pub rollbacks: String,
-    pub up_migrations: String,
-    pub down_migrations: String,
-    pub changes: String,
-    pub migration_why: String,
-    pub authors: String,
-    pub testers: String,
-    pub comments: String,
-    pub notes: String,
-    pub packages: String,
-    pub news_headline: String,
-    pub workflow: String,
-    pub workflows_samples: String,
-    pub technical_considerations: String,
-    pub next_features: String,
-    pub next_motivation: String,
-    pub next_reasons: String,
-    pub next_resources: String,
-}
-
-enum PageIndex {
-    Page0,
-    Page1,
-    Page2,
-    Page3,
-    Page4,
-    Page5,
-    Page6,
-    Page7,
-    Page8,
-}
-
-impl Display for PageIndex {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Page0 => {
-                write!(f, " /dev/null <== page 1/9 ==> Resolution ")
-            }
-            Page1 => {
-                write!(f, " Problematics <== page 2/9 ==> Security ")
-            }
-            Page2 => {
-                write!(f, " Resolution <== page 3/9 ==> Tests ")
-            }
-            Page3 => {
-                write!(f, " Security <== page 4/9 ==> Requirements ")
-            }
-            Page4 => {
-                write!(f, " Tests <== page 5/9 ==> Database ")
-            }
-            Page5 => {
-                write!(f, " Requirements <== page 6/9 ==> Communication ")
-            }
-            Page6 => {
-                write!(f, " Database <== page 7/9 ==> Ideas ")
-            }
-            Page7 => {
-                write!(f, " Communication <== page 8/9 ==> Next ")
-            }
-            Page8 => {
-                write!(f, " Ideas <== page 9/9 ==> /dev/null ")
-            }
-        }
-    }
-}
+///
+/// Below this width the panes of a page are stacked in a single column.
+///
+const NARROW_WIDTH: u16 = 100;
 
 struct Page {
     main_title: &'static str,
     areas: [TextArea<'static>; 4],
-    current_page: PageIndex,
     titles: [&'static str; 4],
     describe: [&'static str; 4],
+    limits: [Limit; 4],
+    requirements: [Requirement; 4],
 }
 
-#[derive(Copy, Clone)]
-struct App {}
+struct App {
+    keymap: KeyMap,
+    profiles: Vec<Profile>,
+    profile: usize,
+    ///
+    /// The profile of the release notes, over their own pages.
+    ///
+    release: Profile,
+    vim: bool,
+    theme: Theme,
+    accessible: bool,
+    snippets: Vec<Snippet>,
+    ///
+    /// The text pre-filled in a field, as `(page, field, text)`.
+    ///
+    defaults: Vec<(usize, usize, String)>,
+    ///
+    /// The committer, pre-filled in the authors.
+    ///
+    identity: Option<String>,
+    trailers: Trailers,
+    signoff: bool,
+    dictionary: Option<Dictionary>,
+}
 impl App {
-    fn new() -> Self {
-        Self {}
-    }
-    fn render_commit<'a>(
-        self,
-        rei: &'a mut Terminal<CrosstermBackend<Stdout>>,
-        areas: &mut Vec<TextArea>,
-        describe: &mut Vec<&'static str>,
-        titles: &mut Vec<&'static str>,
-        main_title: &'static str,
-        index: &PageIndex,
-        witch: usize,
-    ) -> std::io::Result<CompletedFrame<'a>> {
-        for (i, area) in areas.iter_mut().enumerate() {
-            area.set_block(
-                Block::default()
-                    .borders(Borders::all())
-                    .title_alignment(Alignment::Left)
-                    .title(format!(" {} ", titles[i])),
-            );
-            area.set_line_number_style(Style::default().fg(Color::White));
-            area.set_cursor_style(Style::underlined(Style::default().fg(Color::White)));
-            if i.eq(&witch) {
-                activate(area, titles[i], describe[i]);
-            } else {
-                inactivate(area, titles[i], describe[i]);
-            }
+    fn new(config: &Config) -> Result<Self, String> {
+        let profiles = Profile::all(config)?;
+        let profile = match config.form.profile.as_deref() {
+            Some(name) => profiles
+                .iter()
+                .position(|profile| profile.name.eq(name))
+                .ok_or_else(|| format!("unknown profile `{name}`"))?,
+            None => profiles
+                .iter()
+                .position(|profile| profile.name.eq("full"))
+                .unwrap_or(0),
+        };
+        for snippet in &config.snippets {
+            snippet.check()?;
         }
-        rei.draw(|f| {
-            let parent_block = Block::default()
-                .title(format!(" {main_title} "))
-                .title_alignment(Alignment::Center)
-                .title_bottom(format!("{index}"))
-                .title_alignment(Alignment::Center)
-                .borders(Borders::all());
+        let mut defaults: Vec<(usize, usize, String)> = Vec::new();
+        for (field, text) in &config.defaults {
+            let (page, witch) = field_position(field)
+                .ok_or_else(|| format!("defaults: unknown field `{field}`"))?;
+            defaults.push((page, witch, text.clone()));
+        }
+        Ok(Self {
+            keymap: KeyMap::new(&config.keys)?,
+            profiles,
+            profile,
+            release: Profile::release(),
+            vim: config.editor.mode.eq(&EditorMode::Vim),
+            theme: Theme::new(config)?,
+            accessible: config.accessibility.enabled,
+            snippets: config.snippets.clone(),
+            defaults,
+            identity: git::identity(),
+            trailers: Trailers::new(config)?,
+            signoff: config.commit.signoff,
+            dictionary: if config.spell.enabled {
+                Dictionary::find(config.spell.dictionary.as_deref())?
+            } else {
+                None
+            },
+        })
+    }
+}
 
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .vertical_margin(2)
-                .horizontal_margin(4)
-                .spacing(2)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(f.area());
+///
+/// Split the screen into the four panes of a page, indexed like `Page::areas`.
+///
+/// Narrow terminals and the linear layout of the accessibility mode stack the
+/// panes in a single column, the zoom gives all the space to the focused pane
+/// and an empty rectangle to the others.
+///
+fn form_layout(area: Rect, witch: usize, zoom: bool, linear: bool) -> [Rect; 4] {
+    if zoom {
+        let mut chunks = [Rect::default(); 4];
+        chunks[witch] = area.inner(Margin::new(2, 1));
+        return chunks;
+    }
+    if linear || area.width.lt(&NARROW_WIDTH) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .vertical_margin(1)
+            .horizontal_margin(1)
+            .constraints([Constraint::Ratio(1, 4); 4])
+            .split(area);
+        return [chunks[0], chunks[1], chunks[2], chunks[3]];
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .vertical_margin(2)
+        .horizontal_margin(4)
+        .spacing(2)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let left_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+
+    let right_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    [
+        left_chunks[0],
+        right_chunks[0],
+        left_chunks[1],
+        right_chunks[1],
+    ]
+}
 
-            let left_chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(chunks[0]);
+///
+/// A rectangle of at most `width` x `height` in the middle of `area`.
+///
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
 
-            let right_chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(chunks[1]);
-            f.render_widget(parent_block, f.area());
-            f.render_widget(&areas[0], left_chunks[0]);
-            f.render_widget(&areas[2], left_chunks[1]);
+///
+/// A popup with a message, closed by any key.
+///
+fn render_message(f: &mut Frame, message: &str) {
+    let rows: usize = message
+        .lines()
+        .map(|line| line.chars().count().div_ceil(64).max(1))
+        .sum();
+    let height = u16::try_from(rows).unwrap_or(u16::MAX).saturating_add(4);
+    let area = centered(f.area(), 70, height.max(7));
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(message).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::all())
+                .border_type(BorderType::Rounded)
+                .padding(Padding::uniform(1))
+                .title_bottom(" press any key ")
+                .title_alignment(Alignment::Center),
+        ),
+        area,
+    );
+}
 
-            f.render_widget(&areas[1], right_chunks[0]);
-            f.render_widget(&areas[3], right_chunks[1]);
-        })
-    }
+///
+/// The page and the index of the field titled `name`, the case is ignored.
+///
+fn field_position(name: &str) -> Option<(usize, usize)> {
+    new_pages().iter().enumerate().find_map(|(p, page)| {
+        page.titles
+            .iter()
+            .position(|title| title.eq_ignore_ascii_case(name))
+            .map(|witch| (p, witch))
+    })
+}
+
+///
+/// Replace the whole text of the textarea, the change can be undone.
+///
+fn set_text(textarea: &mut TextArea<'_>, text: &str) {
+    let yank = textarea.yank_text();
+    textarea.select_all();
+    textarea.cut();
+    textarea.insert_str(text);
+    textarea.set_yank_text(yank);
 }
 
-fn get_lines(pages: &mut [Page], page_index: PageIndex, area: usize) -> String {
-    let x = pages.get_mut(page_index as usize).unwrap();
-    let y = x.areas.get_mut(area).unwrap();
-    y.lines().join("\n")
+///
+/// The state of a field in words, for the accessibility mode.
+///
+fn indicator(textarea: &TextArea<'_>, limit: Limit) -> String {
+    let longest = textarea
+        .lines()
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.chars().count()))
+        .max_by_key(|(_, length)| *length);
+    match longest {
+        Some((line, length)) if limit.exceeded(length) => {
+            format!("⚠ line {line}: {length} chars")
+        }
+        Some((line, length)) if length.gt(&0) => {
+            format!("✓ longest line {line}: {length} chars")
+        }
+        _ => String::from("○ empty"),
+    }
 }
-fn inactivate(textarea: &mut TextArea<'_>, title: &str, describe: &str) {
+
+fn inactivate(
+    textarea: &mut TextArea<'_>,
+    title: &str,
+    describe: &str,
+    limit: Limit,
+    theme: &Theme,
+    accessible: bool,
+) {
+    let title = if accessible {
+        format!("{title} — {}", indicator(textarea, limit))
+    } else {
+        title.to_string()
+    };
     textarea.set_cursor_line_style(Style::default());
     textarea.set_cursor_style(Style::default());
     textarea.set_block(
         Block::default()
             .borders(Borders::all())
             .border_type(BorderType::Rounded)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(theme.inactive())
             .title(format!(" {title} "))
             .title_bottom(format!(" {describe} ")),
     );
 }
 
-fn update_commit(page: &mut [Page]) -> Commit {
-    Commit {
-        title: get_lines(page, Page0, 0),
-        description: get_lines(page, Page0, 1),
-        steps: get_lines(page, Page0, 2),
-        expected_description: get_lines(page, Page0, 3),
-        system_before: get_lines(page, Page1, 0),
-        system_after: get_lines(page, Page1, 1),
-        expectation: get_lines(page, Page1, 2),
-        samples: get_lines(page, Page1, 3),
-        vulnerabilities: get_lines(page, Page2, 0),
-        qualities: get_lines(page, Page2, 1),
-        conforms: get_lines(page, Page2, 2),
-        risks: get_lines(page, Page2, 3),
-        tests_added: get_lines(page, Page3, 0),
-        tests_updated: get_lines(page, Page3, 1),
-        tests_deleted: get_lines(page, Page3, 2),
-        platforms: get_lines(page, Page3, 3),
-        breaking_changes: get_lines(page, Page4, 0),
-        dependencies: get_lines(page, Page4, 1),
-        packages: get_lines(page, Page4, 2),
-        news_headline: get_lines(page, Page7, 0),
-        workflow: get_lines(page, Page7, 1),
-        workflows_samples: get_lines(page, Page7, 2),
-        technical_considerations: get_lines(page, Page7, 3),
-        next_features: get_lines(page, Page8, 0),
-        next_motivation: get_lines(page, Page8, 1),
-        next_reasons: get_lines(page, Page8, 2),
-        next_resources: get_lines(page, Page8, 3),
-        rollbacks: get_lines(page, Page4, 3),
-        up_migrations: get_lines(page, Page5, 0),
-        down_migrations: get_lines(page, Page5, 1),
-        changes: get_lines(page, Page5, 2),
-        migration_why: get_lines(page, Page5, 3),
-        authors: get_lines(page, Page6, 0),
-        testers: get_lines(page, Page6, 1),
-        comments: get_lines(page, Page6, 2),
-        notes: get_lines(page, Page6, 3),
-    }
-}
-fn update(witch: usize, page: &mut Page) {
-    for (i, area) in page.areas.iter_mut().enumerate() {
-        if witch.eq(&i) {
-            activate(area, page.main_title, page.describe[i]);
-        } else {
-            inactivate(area, page.main_title, page.describe[i]);
-        }
+fn activate(
+    textarea: &mut TextArea<'_>,
+    title: &str,
+    describe: &str,
+    limit: Limit,
+    theme: &Theme,
+    accessible: bool,
+) {
+    let mut block = Block::default();
+    if !accessible {
+        block = block.rapid_blink();
     }
-}
-fn activate(textarea: &mut TextArea<'_>, title: &str, describe: &str) {
-    let mut lines: Vec<usize> = Vec::new();
-    textarea.lines().iter().for_each(|line| {
-        lines.push(line.chars().count());
-    });
-    let block = Block::default()
-        .rapid_blink()
+    let block = block
         .borders(Borders::all())
         .border_type(BorderType::Rounded)
         .padding(Padding::new(0, 2, 0, 2))
         .title_alignment(Alignment::Center)
         .title(format!(" {title} "))
         .title_bottom(format!(" {describe} "));
-    let mut red = false;
-    let mut orange = false;
-    let mut yellow = false;
-    let mut green = false;
-    for line in &lines {
-        if line.gt(&72) {
-            red = true;
-            orange = false;
-            yellow = false;
-            green = false;
-        } else if line.le(&35) && line.ge(&20) {
-            green = true;
-        } else if line.le(&50) && line.ge(&35) {
-            yellow = true;
-        } else if line.lt(&72) && line.ge(&50) {
-            orange = true;
-        }
-    }
-    if red {
-        textarea.set_block(
-            block
-                .style(Style::default().fg(Color::Red))
-                .border_style(Style::default().fg(Color::Red))
-                .title(format!(
-                    " {title} ( a line is superior to the max lines length )"
-                )),
-        );
-    } else if yellow {
-        textarea.set_block(
-            block
-                .style(Style::default().fg(Color::Yellow))
-                .border_style(Style::default().fg(Color::Yellow)),
-        );
-    } else if orange {
-        textarea.set_block(
-            block
-                .style(Style::default().fg(Color::Rgb(255, 165, 0)))
-                .border_style(Style::default().fg(Color::Rgb(255, 165, 0))),
-        );
-    } else if green {
-        textarea.set_block(
-            block
-                .style(Style::default().fg(Color::Green))
-                .border_style(Style::default().fg(Color::Green)),
-        );
+    let longest = textarea
+        .lines()
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let length = limit.length(longest);
+    let style = theme.length(length);
+    let block = block.style(style).border_style(style);
+    if accessible {
+        textarea.set_block(block.title(format!("▶ {}", indicator(textarea, limit))));
+    } else if length.eq(&Length::TooLong) {
+        textarea.set_block(block.title(format!(
+            " {title} ( a line is superior to the max lines length )"
+        )));
     } else {
-        textarea.set_block(
-            block
-                .style(Style::default().fg(Color::White))
-                .border_style(Style::default().fg(Color::White)),
-        );
+        textarea.set_block(block);
     }
 }
 
-fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Result<()> {
-    let mut pages = [
+///
+/// The pages of the commit form, empty.
+///
+fn new_pages() -> [Page; 9] {
+    [
         Page {
             main_title: "Problematic",
             areas: [
@@ -462,7 +336,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page0,
+            limits: [SUBJECT, BODY, CODE, BODY],
+            requirements: [Required, Optional, Optional, Optional],
             titles: ["Title", "Description", "Steps to reproduce", "Expectation"],
             describe: [
                 "Indicate the problem title",
@@ -479,7 +354,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page1,
+            limits: [BODY, BODY, BODY, CODE],
+            requirements: [Optional; 4],
             titles: ["Before", "After", "Results", "Samples"],
             describe: [
                 "Describe the state before the implementation of the resolution",
@@ -496,7 +372,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page2,
+            limits: [BODY; 4],
+            requirements: [Optional; 4],
             titles: ["Vulnerability", "Quality", "Conformity", "Risk"],
             describe: [
                 "Describe potential security vulnerabilities",
@@ -513,7 +390,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page3,
+            limits: [BODY; 4],
+            requirements: [Optional; 4],
             titles: ["Added", "Updated", "Deleted", "Platforms"],
             describe: [
                 "Describe the new tests added",
@@ -530,7 +408,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page4,
+            limits: [BODY; 4],
+            requirements: [Optional, Optional, Optional, When(4, 0)],
             titles: [
                 "Breaking changes",
                 "New needed dependencies",
@@ -552,7 +431,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page5,
+            limits: [CODE, CODE, BODY, BODY],
+            requirements: [Optional, When(5, 0), Optional, Optional],
             titles: ["Up", "Down", "Changes", "Why"],
             describe: [
                 "What's it's created on up",
@@ -569,7 +449,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page6,
+            limits: [BODY; 4],
+            requirements: [Optional; 4],
             titles: ["Authors", "Testers", "Comments", "Notes"],
             describe: [
                 "Authors name's",
@@ -586,7 +467,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page7,
+            limits: [BODY, BODY, CODE, BODY],
+            requirements: [Optional; 4],
             titles: [
                 "News headline",
                 "Workflow samples",
@@ -608,7 +490,8 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 TextArea::default(),
                 TextArea::default(),
             ],
-            current_page: Page8,
+            limits: [BODY; 4],
+            requirements: [Optional; 4],
             titles: [
                 "To Implement",
                 "Motivation",
@@ -622,131 +505,21 @@ fn commit(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) -> std::io::Re
                 "Links to relevant resources",
             ],
         },
-    ];
-    let mut page: usize = 0;
-    let mut witch: usize = 0;
-    let mut commit_message: Commit = update_commit(&mut pages);
-    loop {
-        match pages[page].current_page {
-            Page0 | Page1 | Page2 | Page3 | Page4 | Page5 | Page6 | Page7 | Page8 => {
-                assert!(app
-                    .render_commit(
-                        rei,
-                        &mut pages[page].areas.to_vec(),
-                        &mut pages[page].describe.to_vec(),
-                        &mut pages[page].titles.to_vec(),
-                        pages[page].main_title,
-                        &pages[page].current_page,
-                        witch,
-                    )
-                    .is_ok());
-            }
-        }
-        if let Ok(Event::Key(key)) = event::read() {
-            if key.code == KeyCode::Esc {
-                break;
-            } else if key.code == KeyCode::F(6) {
-                let message = COMMIT_TEMPLATE
-                    .replace("%title%", &commit_message.title)
-                    .replace("%description%", &commit_message.description)
-                    .replace("%steps%", &commit_message.steps)
-                    .replace(
-                        "%expected_description%",
-                        &commit_message.expected_description,
-                    )
-                    .replace("%system_before%", &commit_message.system_before)
-                    .replace("%system_after%", &commit_message.system_after)
-                    .replace("%system_before%", &commit_message.system_before)
-                    .replace("%expectation%", &commit_message.expectation)
-                    .replace("%samples%", &commit_message.samples)
-                    .replace("%vulnerabilities%", &commit_message.vulnerabilities)
-                    .replace("%qualities%", &commit_message.qualities)
-                    .replace("%conformity%", &commit_message.conforms)
-                    .replace("%risk%", &commit_message.risks)
-                    .replace("%tests_added%", &commit_message.tests_added)
-                    .replace("%tests_updated%", &commit_message.tests_updated)
-                    .replace("%test_deleted%", &commit_message.tests_deleted)
-                    .replace("%tested_platforms%", &commit_message.platforms)
-                    .replace("%breaking_changes%", &commit_message.breaking_changes)
-                    .replace("%dependencies%", &commit_message.dependencies)
-                    .replace("%rollbacks%", &commit_message.rollbacks)
-                    .replace("%db_up%", &commit_message.up_migrations)
-                    .replace("%db_down%", &commit_message.down_migrations)
-                    .replace("%db_changes%", &commit_message.changes)
-                    .replace("%why_db_changes%", &commit_message.migration_why)
-                    .replace("%authors%", &commit_message.authors)
-                    .replace("%testers%", &commit_message.testers)
-                    .replace("%comments%", &commit_message.comments)
-                    .replace("%notes%", &commit_message.notes)
-                    .replace("%news_headline%", &commit_message.news_headline)
-                    .replace("%workflow%", &commit_message.workflow)
-                    .replace("%workflows_samples%", &commit_message.workflows_samples)
-                    .replace(
-                        "%technical_considerations%",
-                        &commit_message.technical_considerations,
-                    )
-                    .replace("%next_motivation%", &commit_message.next_motivation)
-                    .replace("%next_description%", &commit_message.next_features)
-                    .replace("%next_reasons%", &commit_message.next_reasons)
-                    .replace("%next_links%", &commit_message.next_resources);
-
-                assert!(Command::new("git")
-                    .stdout(Stdio::null())
-                    .stderr(Stdio::null())
-                    .arg("commit")
-                    .arg("-m")
-                    .arg(message)
-                    .current_dir(".")
-                    .spawn()
-                    .expect("git not founded")
-                    .wait()
-                    .is_ok());
-                commit_message = Commit::default();
-                let _ = commit(rei, app);
-            } else if key.code == KeyCode::PageUp {
-                witch = 0;
-                if page.lt(&(pages.len() - 1)) {
-                    page += 1;
-                }
-            } else if key.code == KeyCode::PageDown {
-                witch = 0;
-                if page.gt(&0) {
-                    page -= 1;
-                }
-            } else if key.code == KeyCode::F(7) {
-                if witch.lt(&3) {
-                    witch += 1;
-                    update(witch, &mut pages[page])
-                }
-            } else if key.code == KeyCode::F(5) {
-                if witch.gt(&0) {
-                    witch -= 1;
-                }
-            } else {
-                pages[page].areas.get_mut(witch).expect("").input(key);
-                commit_message = update_commit(&mut pages);
-            }
-        }
-    }
-    Ok(())
+    ]
 }
 
-fn dojo(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: App) {
-    loop {
-        if let Event::Key(key) = event::read().unwrap() {
-            if key.code == KeyCode::Esc {
-                break;
-            }
-            if key.code == KeyCode::F(2) && commit(rei, app).is_ok() {
-                break;
-            }
-        }
-    }
-}
 fn main() {
-    let app = App::new();
+    let app = match Config::load().and_then(|config| App::new(&config)) {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("rei: {e}");
+            std::process::exit(1);
+        }
+    };
 
     let mut rei = ratatui::init();
-    dojo(&mut rei, app);
+    let _ = execute!(stdout(), EnableMouseCapture);
+    screen::run(&mut rei, &app);
+    let _ = execute!(stdout(), DisableMouseCapture);
     ratatui::restore();
 }
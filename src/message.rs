@@ -0,0 +1,447 @@
+use crate::config::Config;
+use crate::{field_position, Page};
+
+///
+/// A part of the commit message holding a field.
+///
+struct Section {
+    page: usize,
+    field: usize,
+    heading: &'static str,
+    fence: Option<&'static str>,
+}
+
+const fn section(page: usize, field: usize, heading: &'static str) -> Section {
+    Section {
+        page,
+        field,
+        heading,
+        fence: None,
+    }
+}
+
+const fn code(page: usize, field: usize, heading: &'static str, fence: &'static str) -> Section {
+    Section {
+        page,
+        field,
+        heading,
+        fence: Some(fence),
+    }
+}
+
+///
+/// The sections following the title and the description, in message order.
+///
+const SECTIONS: [Section; 34] = [
+    code(0, 2, "Steps", "bash"),
+    section(0, 3, "Behavior"),
+    section(1, 0, "Before"),
+    section(1, 1, "After"),
+    section(1, 2, "Expectation"),
+    section(1, 3, "Examples"),
+    section(2, 0, "Vulnerabilities"),
+    section(2, 1, "Quality"),
+    section(2, 2, "Conformity"),
+    section(2, 3, "Risk"),
+    section(3, 0, "Added"),
+    section(3, 1, "Updated"),
+    section(3, 2, "Deleted"),
+    section(3, 3, "Platforms"),
+    section(4, 0, "Breaking"),
+    section(4, 1, "Dependencies"),
+    section(4, 2, "Packages"),
+    section(4, 3, "Rollback"),
+    code(5, 0, "Up", "sql"),
+    code(5, 1, "Down", "sql"),
+    section(5, 2, "Changes"),
+    section(5, 3, "Why"),
+    section(6, 0, "Authors"),
+    section(6, 1, "Testers"),
+    section(6, 2, "Comments"),
+    section(6, 3, "Notes"),
+    section(7, 0, "Head"),
+    section(7, 1, "Workflow"),
+    section(7, 2, "Samples"),
+    section(7, 3, "Considerations"),
+    section(8, 0, "Next"),
+    section(8, 1, "Motivation"),
+    section(8, 2, "Why"),
+    section(8, 3, "Links"),
+];
+
+const CO_AUTHORED_BY: &str = "Co-authored-by";
+
+///
+/// The fields written as git trailers instead of prose sections, with their
+/// token.
+///
+pub struct Trailers {
+    fields: Vec<(usize, usize, String)>,
+}
+
+impl Trailers {
+    ///
+    /// The authors are `Co-authored-by` trailers unless configured otherwise,
+    /// an empty token keeps a field in the prose.
+    ///
+    /// # Errors
+    ///
+    /// On unknown field.
+    ///
+    pub fn new(config: &Config) -> Result<Self, String> {
+        let mut fields = vec![(6, 0, String::from(CO_AUTHORED_BY))];
+        for (field, token) in &config.trailers {
+            let (page, witch) = field_position(field)
+                .ok_or_else(|| format!("trailers: unknown field `{field}`"))?;
+            fields.retain(|(p, w, _)| (*p, *w).ne(&(page, witch)));
+            if !token.trim().is_empty() {
+                fields.push((page, witch, token.trim().to_string()));
+            }
+        }
+        Ok(Self { fields })
+    }
+
+    fn token(&self, page: usize, field: usize) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(p, w, _)| (*p, *w).eq(&(page, field)))
+            .map(|(_, _, token)| token.as_str())
+    }
+
+    fn field(&self, token: &str) -> Option<(usize, usize)> {
+        self.fields
+            .iter()
+            .find(|(_, _, t)| t.eq_ignore_ascii_case(token))
+            .map(|(p, w, _)| (*p, *w))
+    }
+}
+
+///
+/// A commit message read back, its fields as `(page, field, text)` and the
+/// trailers which belong to no field, kept verbatim.
+///
+pub struct Message {
+    pub fields: Vec<(usize, usize, String)>,
+    pub footer: Vec<String>,
+}
+
+impl IntoIterator for Message {
+    type Item = (usize, usize, String);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Message {
+    type Item = &'a (usize, usize, String);
+    type IntoIter = std::slice::Iter<'a, (usize, usize, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+fn text(page: &Page, field: usize) -> String {
+    page.areas[field].lines().join("\n")
+}
+
+///
+/// The commit message of the form, the pages out of `visible` are left out.
+///
+/// The committer is not repeated in the `Co-authored-by` trailers, the
+/// trailers of `kept` follow those of the fields. With `sparse` the empty
+/// sections are left out, so that a message read back comes out as it was.
+///
+pub fn render(
+    pages: &[Page],
+    visible: &[usize],
+    trailers: &Trailers,
+    identity: Option<&str>,
+    kept: &[String],
+    sparse: bool,
+) -> String {
+    let mut blocks: Vec<String> = vec![text(&pages[0], 0)];
+    let description = text(&pages[0], 1);
+    if visible.contains(&0) && !(sparse && description.trim().is_empty()) {
+        blocks.push(description);
+    }
+    let mut footer: Vec<String> = Vec::new();
+    for section in SECTIONS.iter().filter(|s| visible.contains(&s.page)) {
+        let content = text(&pages[section.page], section.field);
+        if let Some(token) = trailers.token(section.page, section.field) {
+            footer.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .filter(|value| !token.eq(CO_AUTHORED_BY) || identity.ne(&Some(*value)))
+                    .map(|value| format!("{token}: {value}")),
+            );
+            continue;
+        }
+        if sparse && content.trim().is_empty() {
+            continue;
+        }
+        blocks.push(String::from(section.heading));
+        match section.fence {
+            Some(fence) => blocks.push(format!("```{fence}\n{content}\n```")),
+            None => blocks.push(content),
+        }
+    }
+    footer.extend(kept.iter().cloned());
+    if !footer.is_empty() {
+        blocks.push(footer.join("\n"));
+    }
+    blocks.join("\n\n") + "\n"
+}
+
+///
+/// The token and value of a trailer line.
+///
+fn trailer(line: &str) -> Option<(&str, &str)> {
+    let (token, value) = line.split_once(": ")?;
+    (!token.is_empty()
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c.eq(&'-') || c.eq(&' ')))
+    .then_some((token, value.trim()))
+}
+
+fn push(fields: &mut Vec<(usize, usize, String)>, page: usize, field: usize, lines: &[&str]) {
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    if let (Some(start), Some(end)) = (start, end) {
+        fields.push((page, field, lines[start..=end].join("\n")));
+    }
+}
+
+///
+/// The fields of a commit message written by [`render`], trailers included.
+///
+pub fn parse(message: &str, trailers: &Trailers) -> Message {
+    let mut lines: Vec<&str> = message.lines().collect();
+    let mut fields: Vec<(usize, usize, String)> = Vec::new();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let footer = lines
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map_or(lines.len(), |blank| blank + 1);
+    let mut found: Vec<(usize, usize, String)> = Vec::new();
+    let mut kept: Vec<String> = Vec::new();
+    if footer.gt(&1)
+        && footer.lt(&lines.len())
+        && lines[footer..].iter().all(|line| trailer(line).is_some())
+    {
+        for line in &lines[footer..] {
+            let Some((token, value)) = trailer(line) else {
+                continue;
+            };
+            let Some((page, field)) = trailers.field(token) else {
+                kept.push(line.trim_end().to_string());
+                continue;
+            };
+            match found
+                .iter_mut()
+                .find(|(p, w, _)| (*p, *w).eq(&(page, field)))
+            {
+                Some((_, _, text)) => {
+                    text.push('\n');
+                    text.push_str(value);
+                }
+                None => found.push((page, field, value.to_string())),
+            }
+        }
+        lines.truncate(footer);
+    }
+    let Some((title, body)) = lines.split_first() else {
+        return Message {
+            fields: found,
+            footer: kept,
+        };
+    };
+    fields.push((0, 0, (*title).to_string()));
+    let mut current: Option<usize> = None;
+    let mut start = 0;
+    for (i, line) in body.iter().enumerate() {
+        let alone = (i.eq(&0) || body[i - 1].trim().is_empty())
+            && body.get(i + 1).is_none_or(|next| next.trim().is_empty());
+        if !alone {
+            continue;
+        }
+        let Some(index) = SECTIONS
+            .iter()
+            .enumerate()
+            .skip(current.map_or(0, |c| c + 1))
+            .find(|(_, s)| s.heading.eq(line.trim()))
+            .map(|(index, _)| index)
+        else {
+            continue;
+        };
+        match current {
+            Some(c) => push(
+                &mut fields,
+                SECTIONS[c].page,
+                SECTIONS[c].field,
+                &unfence(&body[start..i]),
+            ),
+            None => push(&mut fields, 0, 1, &body[start..i]),
+        }
+        current = Some(index);
+        start = i + 1;
+    }
+    let content = &body[start..];
+    match current {
+        Some(c) => push(
+            &mut fields,
+            SECTIONS[c].page,
+            SECTIONS[c].field,
+            &unfence(content),
+        ),
+        None => push(&mut fields, 0, 1, content),
+    }
+    fields.extend(found);
+    Message {
+        fields,
+        footer: kept,
+    }
+}
+
+///
+/// The lines of a section without its surrounding code fence.
+///
+fn unfence<'a>(content: &[&'a str]) -> Vec<&'a str> {
+    let lines: Vec<&str> = content
+        .iter()
+        .copied()
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |end| end + 1);
+    let lines = &lines[..end];
+    match (lines.first(), lines.last()) {
+        (Some(first), Some(last))
+            if lines.len().ge(&2) && first.starts_with("```") && last.trim().eq("```") =>
+        {
+            lines[1..lines.len() - 1].to_vec()
+        }
+        _ => lines.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_pages, set_text};
+
+    fn trailers() -> Trailers {
+        Trailers::new(&Config::default()).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut pages = new_pages();
+        let fields = [
+            (0, 0, "Fix the parser"),
+            (0, 1, "It dropped a line."),
+            (0, 2, "cargo run\ncargo test"),
+            (5, 3, "The first why."),
+            (8, 2, "The second why."),
+            (6, 0, "Ann <ann@example.com>\nBob <bob@example.com>"),
+        ];
+        for (page, field, text) in fields {
+            set_text(&mut pages[page].areas[field], text);
+        }
+        let kept = [String::from("Signed-off-by: Ann <ann@example.com>")];
+        let visible: Vec<usize> = (0..pages.len()).collect();
+        let text = render(&pages, &visible, &trailers(), None, &kept, false);
+        assert!(text.contains("Steps\n\n```bash\ncargo run\ncargo test\n```"));
+        assert!(text.ends_with(
+            "Co-authored-by: Ann <ann@example.com>\nCo-authored-by: Bob <bob@example.com>\nSigned-off-by: Ann <ann@example.com>\n"
+        ));
+        let message = parse(&text, &trailers());
+        let expected: Vec<(usize, usize, String)> = fields
+            .iter()
+            .map(|(page, field, text)| (*page, *field, (*text).to_string()))
+            .collect();
+        assert_eq!(message.fields, expected);
+        assert_eq!(message.footer, kept);
+    }
+
+    #[test]
+    fn identity() {
+        let mut pages = new_pages();
+        set_text(&mut pages[0].areas[0], "Title");
+        set_text(&mut pages[6].areas[0], "Ann <ann@example.com>");
+        let text = render(
+            &pages,
+            &[0, 6],
+            &trailers(),
+            Some("Ann <ann@example.com>"),
+            &[],
+            false,
+        );
+        assert!(!text.contains("Co-authored-by"));
+    }
+
+    #[test]
+    fn sparse() {
+        let mut pages = new_pages();
+        let text = "Title\n\nBody\n\nRefs: #12\n";
+        let message = parse(text, &trailers());
+        for (page, field, content) in &message {
+            set_text(&mut pages[*page].areas[*field], content);
+        }
+        let visible: Vec<usize> = (0..pages.len()).collect();
+        let render = |sparse| render(&pages, &visible, &trailers(), None, &message.footer, sparse);
+        assert_eq!(render(true), text);
+        assert!(render(false).contains("\n\nSteps\n\n```bash\n\n```\n\n"));
+    }
+
+    #[test]
+    fn unknown_trailers() {
+        let message = parse(
+            "Title\n\nBody\n\nRefs: #12\nReviewed-by: Bob <bob@example.com>\n",
+            &trailers(),
+        );
+        assert_eq!(
+            message.fields,
+            vec![(0, 0, String::from("Title")), (0, 1, String::from("Body"))]
+        );
+        assert_eq!(
+            message.footer,
+            vec![
+                String::from("Refs: #12"),
+                String::from("Reviewed-by: Bob <bob@example.com>")
+            ]
+        );
+    }
+
+    #[test]
+    fn prose_footer() {
+        let message = parse("Title\n\nBody\n\nNot: a trailer\nat all\n", &trailers());
+        assert_eq!(
+            message.fields,
+            vec![
+                (0, 0, String::from("Title")),
+                (0, 1, String::from("Body\n\nNot: a trailer\nat all"))
+            ]
+        );
+        assert!(message.footer.is_empty());
+    }
+
+    #[test]
+    fn unfence_code() {
+        assert_eq!(
+            unfence(&["", "```sql", "SELECT 1;", "```", ""]),
+            vec!["SELECT 1;"]
+        );
+        assert_eq!(unfence(&["```", "open"]), vec!["```", "open"]);
+        assert_eq!(unfence(&["plain", ""]), vec!["plain"]);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::form_layout;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+const SCROLL_ROWS: i16 = 3;
+
+pub enum MouseAction {
+    Nothing,
+    Focus(usize),
+    PreviousPage,
+    NextPage,
+    Scroll(usize, i16),
+}
+
+///
+/// Translate a mouse event on the commit form into an action.
+///
+/// `footer` is the page navigation drawn centered on the bottom border,
+/// clicking its left half goes back and its right half goes forward.
+///
+pub fn mouse_action(
+    mouse: MouseEvent,
+    screen: Rect,
+    footer: &str,
+    witch: usize,
+    zoom: bool,
+    linear: bool,
+) -> MouseAction {
+    let position = Position::new(mouse.column, mouse.row);
+    let hovered = form_layout(screen, witch, zoom, linear)
+        .iter()
+        .position(|chunk| chunk.contains(position));
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(i) = hovered {
+                return MouseAction::Focus(i);
+            }
+            if mouse.row.ne(&screen.bottom().saturating_sub(1)) {
+                return MouseAction::Nothing;
+            }
+            let width = u16::try_from(footer.chars().count()).unwrap_or(u16::MAX);
+            let center = screen.x + screen.width / 2;
+            if mouse.column.lt(&center.saturating_sub(width / 2))
+                || mouse.column.ge(&(center + width / 2))
+            {
+                MouseAction::Nothing
+            } else if mouse.column.lt(&center) {
+                MouseAction::PreviousPage
+            } else {
+                MouseAction::NextPage
+            }
+        }
+        MouseEventKind::ScrollDown => MouseAction::Scroll(hovered.unwrap_or(witch), SCROLL_ROWS),
+        MouseEventKind::ScrollUp => MouseAction::Scroll(hovered.unwrap_or(witch), -SCROLL_ROWS),
+        _ => MouseAction::Nothing,
+    }
+}
@@ -0,0 +1,71 @@
+use crate::prompt::{Picker, PickerAction};
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+
+///
+/// How the commit is made.
+///
+#[derive(Copy, Clone)]
+pub struct CommitOptions {
+    pub signoff: bool,
+    pub sign: bool,
+}
+
+pub enum OptionsAction {
+    Stay,
+    Close,
+    Commit(CommitOptions),
+}
+
+///
+/// The options asked before committing.
+///
+pub struct OptionsDialog {
+    options: CommitOptions,
+    format: &'static str,
+    picker: Picker,
+}
+
+impl OptionsDialog {
+    pub fn new(options: CommitOptions, format: &'static str) -> Self {
+        Self {
+            options,
+            format,
+            picker: Picker::new("Commit", " space toggle  ⏎ commit  esc cancel ", 60),
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> OptionsAction {
+        if key.code.eq(&KeyCode::Char(' ')) {
+            if self.picker.selected().eq(&0) {
+                self.options.signoff = !self.options.signoff;
+            } else {
+                self.options.sign = !self.options.sign;
+            }
+            return OptionsAction::Stay;
+        }
+        match self.picker.handle(key, 2) {
+            PickerAction::Stay => OptionsAction::Stay,
+            PickerAction::Close => OptionsAction::Close,
+            PickerAction::Choose(_) => OptionsAction::Commit(self.options),
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let check = |on: bool| if on { "[x]" } else { "[ ]" };
+        let items = vec![
+            ListItem::new(format!(
+                "{} Add a Signed-off-by trailer",
+                check(self.options.signoff)
+            )),
+            ListItem::new(format!(
+                "{} Sign with the {} key",
+                check(self.options.sign),
+                self.format
+            )),
+        ];
+        self.picker.render(f, theme, items);
+    }
+}
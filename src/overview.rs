@@ -0,0 +1,163 @@
+use crate::limit::Limit;
+use crate::profile::Profile;
+use crate::theme::{Length, Theme};
+use crate::Page;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Alignment;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Padding};
+use ratatui::Frame;
+use tui_textarea::TextArea;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum FieldState {
+    Empty,
+    Filled,
+    Invalid,
+}
+
+impl FieldState {
+    pub fn of(textarea: &TextArea<'_>, limit: Limit) -> Self {
+        if textarea
+            .lines()
+            .iter()
+            .any(|line| limit.exceeded(line.chars().count()))
+        {
+            Self::Invalid
+        } else if textarea.lines().iter().all(|line| line.trim().is_empty()) {
+            Self::Empty
+        } else {
+            Self::Filled
+        }
+    }
+
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Empty => "○",
+            Self::Filled => "●",
+            Self::Invalid => "✗",
+        }
+    }
+
+    const fn word(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Filled => "filled",
+            Self::Invalid => "line too long",
+        }
+    }
+
+    fn style(self, theme: &Theme) -> Style {
+        match self {
+            Self::Empty => theme.inactive(),
+            Self::Filled => theme.length(Length::Short),
+            Self::Invalid => theme.length(Length::TooLong),
+        }
+    }
+}
+
+///
+/// The table of contents of the commit form, listing every field of every page.
+///
+pub struct Overview {
+    selected: usize,
+}
+
+pub enum OverviewAction {
+    Stay,
+    Close,
+    Jump(usize, usize),
+}
+
+impl Overview {
+    pub fn new(profile: &Profile, page: usize, witch: usize) -> Self {
+        Self {
+            selected: profile.position(page) * 4 + witch,
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent, profile: &Profile) -> OverviewAction {
+        let fields = profile.pages.len() * 4;
+        match key.code {
+            KeyCode::Esc => OverviewAction::Close,
+            KeyCode::Enter => {
+                OverviewAction::Jump(profile.pages[self.selected / 4], self.selected % 4)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected.lt(&(fields - 1)) {
+                    self.selected += 1;
+                }
+                OverviewAction::Stay
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if self.selected.gt(&0) {
+                    self.selected -= 1;
+                }
+                OverviewAction::Stay
+            }
+            KeyCode::PageDown => {
+                self.selected = (self.selected / 4 + 1).min(profile.pages.len() - 1) * 4;
+                OverviewAction::Stay
+            }
+            KeyCode::PageUp => {
+                self.selected = (self.selected / 4).saturating_sub(1) * 4;
+                OverviewAction::Stay
+            }
+            _ => OverviewAction::Stay,
+        }
+    }
+
+    pub fn render(
+        &self,
+        f: &mut Frame,
+        pages: &[Page],
+        profile: &Profile,
+        theme: &Theme,
+        accessible: bool,
+    ) {
+        let mut items: Vec<ListItem> = Vec::new();
+        let mut row: usize = 0;
+        for (p, page) in profile.pages.iter().map(|p| &pages[*p]).enumerate() {
+            items.push(ListItem::new(Line::from(
+                format!(" {}/{} {} ", p + 1, profile.pages.len(), page.main_title).bold(),
+            )));
+            for (i, area) in page.areas.iter().enumerate() {
+                let state = FieldState::of(area, page.limits[i]);
+                if self.selected.eq(&(p * 4 + i)) {
+                    row = items.len();
+                }
+                items.push(ListItem::new(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(state.symbol(), state.style(theme)),
+                    Span::raw(format!(" {}", page.titles[i])),
+                    Span::raw(if page.requirements[i].is_required(pages) {
+                        " *"
+                    } else {
+                        ""
+                    }),
+                    Span::raw(if accessible {
+                        format!(" ({})", state.word())
+                    } else {
+                        String::new()
+                    }),
+                ])));
+            }
+        }
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::all())
+                    .border_type(BorderType::Rounded)
+                    .padding(Padding::horizontal(2))
+                    .title(" Overview ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(" ● filled  ○ empty  ✗ line too long  * required  ⏎ jump ")
+                    .title_alignment(Alignment::Center),
+            )
+            .style(theme.text())
+            .highlight_style(theme.selection());
+        let mut state = ListState::default().with_selected(Some(row));
+        f.render_stateful_widget(list, f.area(), &mut state);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::config::Config;
+use crate::prompt::{Picker, PickerAction};
+use crate::theme::Theme;
+use crate::{new_pages, Page};
+use crossterm::event::KeyEvent;
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+
+///
+/// A named selection of the form pages.
+///
+pub struct Profile {
+    pub name: String,
+    pub describe: String,
+    pub pages: Vec<usize>,
+}
+
+impl Profile {
+    fn builtin(name: &str, describe: &str, pages: &[usize]) -> Self {
+        Self {
+            name: name.to_string(),
+            describe: describe.to_string(),
+            pages: pages.to_vec(),
+        }
+    }
+
+    ///
+    /// The built-in profiles followed by the user ones, a user profile with the
+    /// name of a built-in one replaces it. The first page, which holds the
+    /// title, is always in.
+    ///
+    /// # Errors
+    ///
+    /// On empty profile or unknown page name.
+    ///
+    pub fn all(config: &Config) -> Result<Vec<Self>, String> {
+        let titles: Vec<&str> = new_pages().iter().map(|page| page.main_title).collect();
+        let mut profiles = vec![
+            Self::builtin("quick", "Problematic page only, for a small fix", &[0]),
+            Self::builtin("standard", "Problematic, resolution and tests", &[0, 1, 3]),
+            Self::builtin(
+                "full",
+                "Every page",
+                &(0..titles.len()).collect::<Vec<usize>>(),
+            ),
+        ];
+        for (name, profile) in &config.profiles {
+            let mut pages: Vec<usize> = Vec::new();
+            for page in &profile.pages {
+                pages.push(
+                    titles
+                        .iter()
+                        .position(|title| title.eq_ignore_ascii_case(page))
+                        .ok_or_else(|| format!("profile `{name}`: unknown page `{page}`"))?,
+                );
+            }
+            if pages.is_empty() {
+                return Err(format!("profile `{name}` has no page"));
+            }
+            pages.push(0);
+            pages.sort_unstable();
+            pages.dedup();
+            let profile = Self {
+                name: name.clone(),
+                describe: profile.description.clone().unwrap_or_default(),
+                pages,
+            };
+            if let Some(builtin) = profiles.iter_mut().find(|p| p.name.eq(name)) {
+                *builtin = profile;
+            } else {
+                profiles.push(profile);
+            }
+        }
+        Ok(profiles)
+    }
+
+    pub fn release() -> Self {
+        Self::builtin("release", "The notes of a release tag", &[0, 1])
+    }
+
+    pub fn first(&self) -> usize {
+        self.pages[0]
+    }
+
+    pub fn next(&self, page: usize) -> Option<usize> {
+        self.pages.iter().copied().find(|p| p.gt(&page))
+    }
+
+    pub fn previous(&self, page: usize) -> Option<usize> {
+        self.pages.iter().rev().copied().find(|p| p.lt(&page))
+    }
+
+    pub fn position(&self, page: usize) -> usize {
+        self.pages.iter().position(|p| p.eq(&page)).unwrap_or(0)
+    }
+
+    ///
+    /// The page navigation drawn at the bottom of the form.
+    ///
+    pub fn footer(&self, pages: &[Page], page: usize) -> String {
+        let name = |p: Option<usize>| p.map_or("/dev/null", |p| pages[p].main_title);
+        format!(
+            " {} <== page {}/{} ==> {} ",
+            name(self.previous(page)),
+            self.position(page) + 1,
+            self.pages.len(),
+            name(self.next(page))
+        )
+    }
+}
+
+///
+/// The profile showing every page of the fields, the current one when it
+/// does, else the one with the most pages.
+///
+pub fn covering<'a>(
+    profiles: &'a [Profile],
+    current: usize,
+    fields: &[(usize, usize, String)],
+) -> &'a Profile {
+    let covers = |profile: &&Profile| {
+        fields
+            .iter()
+            .all(|(page, _, _)| profile.pages.contains(page))
+    };
+    profiles
+        .get(current)
+        .filter(covers)
+        .or_else(|| profiles.iter().find(covers))
+        .or_else(|| profiles.iter().max_by_key(|profile| profile.pages.len()))
+        .unwrap_or(&profiles[current])
+}
+
+pub enum ProfileAction {
+    Stay,
+    Close,
+    Open(usize),
+}
+
+///
+/// The choice of the profile when opening the form.
+///
+pub struct ProfilePicker {
+    picker: Picker,
+}
+
+impl ProfilePicker {
+    pub fn new(selected: usize) -> Self {
+        Self {
+            picker: Picker::new("Commit profile", " ⏎ open  esc close ", 70)
+                .with_selected(selected),
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent, profiles: &[Profile]) -> ProfileAction {
+        match self.picker.handle(key, profiles.len()) {
+            PickerAction::Stay => ProfileAction::Stay,
+            PickerAction::Close => ProfileAction::Close,
+            PickerAction::Choose(profile) => ProfileAction::Open(profile),
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, profiles: &[Profile], theme: &Theme) {
+        let items: Vec<ListItem> = profiles
+            .iter()
+            .map(|profile| {
+                ListItem::new(format!(
+                    "{:<12} {} ({} pages)",
+                    profile.name,
+                    profile.describe,
+                    profile.pages.len()
+                ))
+            })
+            .collect();
+        self.picker.render(f, theme, items);
+    }
+}
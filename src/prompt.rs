@@ -0,0 +1,198 @@
+use crate::centered;
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Alignment;
+use ratatui::widgets::{
+    Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+};
+use ratatui::Frame;
+
+pub enum PromptAction {
+    Stay,
+    Cancel,
+    Submit(String),
+}
+
+///
+/// A single line asked to the user, like a branch name.
+///
+pub struct Prompt {
+    title: String,
+    value: String,
+}
+
+impl Prompt {
+    pub fn new(title: &str, value: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> PromptAction {
+        match key.code {
+            KeyCode::Esc => PromptAction::Cancel,
+            KeyCode::Enter => PromptAction::Submit(self.value.trim().to_string()),
+            KeyCode::Backspace => {
+                self.value.pop();
+                PromptAction::Stay
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.value.clear();
+                PromptAction::Stay
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.value.push(c);
+                PromptAction::Stay
+            }
+            _ => PromptAction::Stay,
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let area = centered(f.area(), 70, 3);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(format!("{}▏", self.value))
+                .style(theme.text())
+                .block(
+                    Block::default()
+                        .borders(Borders::all())
+                        .border_type(BorderType::Rounded)
+                        .padding(Padding::horizontal(1))
+                        .title(format!(" {} ", self.title))
+                        .title_alignment(Alignment::Center)
+                        .title_bottom(" ⏎ ok  esc cancel ")
+                        .title_alignment(Alignment::Center),
+                ),
+            area,
+        );
+    }
+}
+
+///
+/// A yes or no question, for the actions losing work.
+///
+pub struct Confirm {
+    question: String,
+}
+
+impl Confirm {
+    pub fn new(question: &str) -> Self {
+        Self {
+            question: question.to_string(),
+        }
+    }
+
+    ///
+    /// `Some(true)` on yes, `Some(false)` on no, `None` for other keys.
+    ///
+    pub const fn handle(key: KeyEvent) -> Option<bool> {
+        match key.code {
+            KeyCode::Char('y' | 'Y') => Some(true),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let area = centered(f.area(), 70, 6);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(self.question.as_str())
+                .wrap(Wrap { trim: true })
+                .style(theme.text())
+                .block(
+                    Block::default()
+                        .borders(Borders::all())
+                        .border_type(BorderType::Rounded)
+                        .padding(Padding::horizontal(1))
+                        .title_bottom(" y yes  n no ")
+                        .title_alignment(Alignment::Center),
+                ),
+            area,
+        );
+    }
+}
+
+pub enum PickerAction {
+    Stay,
+    Close,
+    Choose(usize),
+}
+
+///
+/// A popup list to choose an item from, the items are given when drawn.
+///
+pub struct Picker {
+    title: String,
+    keys: &'static str,
+    width: u16,
+    selected: usize,
+}
+
+impl Picker {
+    pub fn new(title: &str, keys: &'static str, width: u16) -> Self {
+        Self {
+            title: title.to_string(),
+            keys,
+            width,
+            selected: 0,
+        }
+    }
+
+    pub const fn with_selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub const fn selected(&self) -> usize {
+        self.selected
+    }
+
+    ///
+    /// Move in the `len` items or choose one.
+    ///
+    pub fn handle(&mut self, key: KeyEvent, len: usize) -> PickerAction {
+        match key.code {
+            KeyCode::Esc => PickerAction::Close,
+            KeyCode::Enter if self.selected.lt(&len) => PickerAction::Choose(self.selected),
+            KeyCode::Enter => PickerAction::Close,
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected.lt(&len.saturating_sub(1)) {
+                    self.selected += 1;
+                }
+                PickerAction::Stay
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                PickerAction::Stay
+            }
+            _ => PickerAction::Stay,
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme, items: Vec<ListItem>) {
+        let height = u16::try_from(items.len())
+            .unwrap_or(u16::MAX)
+            .saturating_add(4)
+            .min(f.area().height);
+        let area = centered(f.area(), self.width, height);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::all())
+                    .border_type(BorderType::Rounded)
+                    .padding(Padding::horizontal(1))
+                    .title(format!(" {} ", self.title))
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(self.keys)
+                    .title_alignment(Alignment::Center),
+            )
+            .style(theme.text())
+            .highlight_style(theme.selection());
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
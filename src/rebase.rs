@@ -0,0 +1,488 @@
+use crate::complete::PEOPLE;
+use crate::external;
+use crate::git::{self, Commit};
+use crate::message::{self, Message, Trailers};
+use crate::prompt::{Confirm, Prompt, PromptAction};
+use crate::{render_message, App};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use std::fs;
+use std::path::PathBuf;
+
+///
+/// What the rebase does with a commit.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Command {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl Command {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Edit => "edit",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    ///
+    /// Whether the commit is melted in the one before.
+    ///
+    const fn melts(self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+struct Entry {
+    commit: Commit,
+    command: Command,
+    ///
+    /// The message written in the form, for a rewording or the commit the
+    /// next ones are squashed in.
+    ///
+    message: Option<String>,
+}
+
+///
+/// Several commit messages in one, the sections are put one after the other
+/// and the people and the other trailers are listed once.
+///
+fn merge(messages: &[&str], trailers: &Trailers) -> Message {
+    let mut merged: Vec<(usize, usize, String)> = Vec::new();
+    let mut footer: Vec<String> = Vec::new();
+    for text in messages {
+        let parsed = message::parse(text, trailers);
+        for line in parsed.footer {
+            if !footer.contains(&line) {
+                footer.push(line);
+            }
+        }
+        for (p, w, content) in parsed.fields {
+            let Some((_, _, existing)) = merged
+                .iter_mut()
+                .find(|(page, field, _)| (*page, *field).eq(&(p, w)))
+            else {
+                merged.push((p, w, content));
+                continue;
+            };
+            if (p, w).eq(&(0, 0)) {
+                continue;
+            }
+            if PEOPLE.contains(&(p, w)) {
+                for line in content.lines() {
+                    if !existing.lines().any(|known| known.eq(line)) {
+                        existing.push('\n');
+                        existing.push_str(line);
+                    }
+                }
+            } else if !existing.contains(&content) {
+                existing.push_str("\n\n");
+                existing.push_str(&content);
+            }
+        }
+    }
+    Message {
+        fields: merged,
+        footer,
+    }
+}
+
+pub enum RebaseAction {
+    Stay,
+    Back,
+    ///
+    /// Write the message of the selected commit in the form.
+    ///
+    Reword(Message),
+}
+
+///
+/// The todo list of an interactive rebase, the messages of the reworded and
+/// squashed commits are written in the form.
+///
+pub struct Rebase {
+    base: String,
+    entries: Vec<Entry>,
+    selected: usize,
+    ///
+    /// The entry whose message is in the form.
+    ///
+    writing: Option<usize>,
+    prompt: Option<Prompt>,
+    confirm: Option<Confirm>,
+    notice: Option<String>,
+    message: Option<String>,
+}
+
+impl Rebase {
+    pub fn new() -> Self {
+        let base = git::upstream().unwrap_or_else(|| String::from("HEAD~10"));
+        Self {
+            prompt: Some(Prompt::new("Rebase onto", &base)),
+            base,
+            entries: Vec::new(),
+            selected: 0,
+            writing: None,
+            confirm: None,
+            notice: None,
+            message: None,
+        }
+    }
+
+    fn load(&mut self, base: String) {
+        match git::commits(&base) {
+            Some(commits) if commits.is_empty() => {
+                self.message = Some(format!("No commit after {base}."));
+                self.prompt = Some(Prompt::new("Rebase onto", &base));
+            }
+            Some(commits) => {
+                self.entries = commits
+                    .into_iter()
+                    .map(|commit| Entry {
+                        commit,
+                        command: Command::Pick,
+                        message: None,
+                    })
+                    .collect();
+                self.selected = 0;
+                self.base = base;
+            }
+            None => {
+                self.message = Some(format!("{base} is not a commit of the history."));
+                self.prompt = Some(Prompt::new("Rebase onto", &base));
+            }
+        }
+    }
+
+    ///
+    /// The entry the selected one is squashed in.
+    ///
+    fn target(&self) -> Option<usize> {
+        self.entries[..self.selected]
+            .iter()
+            .rposition(|entry| !entry.command.melts() && entry.command.ne(&Command::Drop))
+    }
+
+    ///
+    /// The entries squashed in `target`.
+    ///
+    fn group(&self, target: usize) -> impl Iterator<Item = &Entry> {
+        self.entries[target + 1..]
+            .iter()
+            .take_while(|entry| entry.command.melts() || entry.command.eq(&Command::Drop))
+            .filter(|entry| entry.command.eq(&Command::Squash))
+    }
+
+    fn text(entry: &Entry) -> &str {
+        entry.message.as_deref().unwrap_or(&entry.commit.message)
+    }
+
+    fn reword(&mut self, app: &App) -> RebaseAction {
+        let entry = &mut self.entries[self.selected];
+        entry.command = Command::Reword;
+        self.writing = Some(self.selected);
+        RebaseAction::Reword(message::parse(Self::text(entry), &app.trailers))
+    }
+
+    fn squash(&mut self, app: &App) -> RebaseAction {
+        let Some(target) = self.target() else {
+            self.notice = Some(String::from("The first commit can't be squashed."));
+            return RebaseAction::Stay;
+        };
+        self.entries[self.selected].command = Command::Squash;
+        let mut messages = vec![Self::text(&self.entries[target])];
+        messages.extend(
+            self.group(target)
+                .map(|entry| entry.commit.message.as_str()),
+        );
+        let merged = merge(&messages, &app.trailers);
+        self.writing = Some(target);
+        RebaseAction::Reword(merged)
+    }
+
+    ///
+    /// Keep the message written in the form, `None` when the form is left
+    /// without it.
+    ///
+    pub fn written(&mut self, message: Option<String>) {
+        if let (Some(index), Some(message)) = (self.writing.take(), message) {
+            self.entries[index].message = Some(message);
+        }
+    }
+
+    ///
+    /// The todo list given to git, the written messages are amended by `exec`
+    /// lines after the commits squashed in them, then removed. The files of
+    /// the messages are pushed to `paths`, they are left while the rebase
+    /// stops before their amend.
+    ///
+    fn todo(&self, paths: &mut Vec<PathBuf>) -> Result<String, String> {
+        let mut lines: Vec<String> = Vec::new();
+        let mut amend: Option<String> = None;
+        let mut squashing = false;
+        for (i, entry) in self.entries.iter().enumerate() {
+            if !entry.command.melts() && entry.command.ne(&Command::Drop) {
+                lines.extend(amend.take());
+                squashing = entry.message.is_some();
+            }
+            let command = match entry.command {
+                Command::Squash if squashing => Command::Fixup,
+                Command::Reword if entry.message.is_some() => Command::Pick,
+                command => command,
+            };
+            lines.push(format!(
+                "{} {} {}",
+                command.name(),
+                entry.commit.hash,
+                entry.commit.message.lines().next().unwrap_or_default()
+            ));
+            if let Some(message) = &entry.message {
+                let path = external::temp_file(&format!("message-{i}"), message)?;
+                amend = Some(format!(
+                    "exec git commit --amend --only --no-verify --cleanup=verbatim --file {0} && rm -f {0}",
+                    external::quote(&path)
+                ));
+                paths.push(path);
+            }
+        }
+        lines.extend(amend);
+        Ok(lines.join("\n") + "\n")
+    }
+
+    fn run(&mut self) {
+        let mut paths = Vec::new();
+        let result = self.todo(&mut paths).and_then(|todo| {
+            let path = external::temp_file("todo", &todo)?;
+            let result = git::run(&[
+                "-c",
+                &format!("sequence.editor=cp {}", external::quote(&path)),
+                "-c",
+                "core.editor=true",
+                "rebase",
+                "--interactive",
+                &self.base,
+            ]);
+            let _ = fs::remove_file(&path);
+            result
+        });
+        if git::operation().is_none() {
+            for path in &paths {
+                let _ = fs::remove_file(path);
+            }
+        }
+        self.message = Some(match result {
+            Ok(output) if git::operation().is_some() => {
+                format!("{output}\n\nThe rebase stopped, continue it from the conflicts screen.")
+            }
+            Ok(output) => output,
+            Err(output) => format!(
+                "{output}\n\nWhen the rebase stopped on conflicts, resolve them from the conflicts screen."
+            ),
+        });
+        self.entries.clear();
+    }
+
+    pub fn handle(&mut self, app: &App, key: KeyEvent) -> RebaseAction {
+        self.notice = None;
+        if self.message.take().is_some() {
+            return if self.entries.is_empty() && self.prompt.is_none() {
+                RebaseAction::Back
+            } else {
+                RebaseAction::Stay
+            };
+        }
+        if let Some(mut prompt) = self.prompt.take() {
+            match prompt.handle(key) {
+                PromptAction::Stay => self.prompt = Some(prompt),
+                PromptAction::Cancel => return RebaseAction::Back,
+                PromptAction::Submit(base) => self.load(base),
+            }
+            return RebaseAction::Stay;
+        }
+        if let Some(confirm) = self.confirm.take() {
+            match Confirm::handle(key) {
+                None => self.confirm = Some(confirm),
+                Some(true) => self.run(),
+                Some(false) => {}
+            }
+            return RebaseAction::Stay;
+        }
+        let last = self.entries.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return RebaseAction::Back,
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('J') if self.selected.lt(&last) => {
+                self.entries.swap(self.selected, self.selected + 1);
+                self.selected += 1;
+            }
+            KeyCode::Char('K') if self.selected.gt(&0) => {
+                self.entries.swap(self.selected, self.selected - 1);
+                self.selected -= 1;
+            }
+            KeyCode::Char('p') => self.entries[self.selected].command = Command::Pick,
+            KeyCode::Char('e') => self.entries[self.selected].command = Command::Edit,
+            KeyCode::Char('d') => self.entries[self.selected].command = Command::Drop,
+            KeyCode::Char('f') => {
+                if self.target().is_some() {
+                    self.entries[self.selected].command = Command::Fixup;
+                } else {
+                    self.notice = Some(String::from("The first commit can't be squashed."));
+                }
+            }
+            KeyCode::Char('r') => return self.reword(app),
+            KeyCode::Char('s') => return self.squash(app),
+            KeyCode::Enter => {
+                self.confirm = Some(Confirm::new(&format!(
+                    "Rebase the {} commits onto {}?",
+                    self.entries.len(),
+                    self.base
+                )));
+            }
+            _ => {}
+        }
+        RebaseAction::Stay
+    }
+
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_type(BorderType::Rounded)
+            .title(format!(" Rebase onto {} ", self.base))
+            .title_alignment(Alignment::Center)
+            .title_bottom(
+                " p pick  r reword  e edit  s squash  f fixup  d drop  J K move  ⏎ rebase  esc back ",
+            )
+            .title_alignment(Alignment::Center)
+            .style(app.theme.text());
+        let inner = block.inner(f.area());
+        f.render_widget(block, f.area());
+        let [list, notice] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let rows: Vec<Row> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let subject = entry
+                    .message
+                    .as_deref()
+                    .unwrap_or(&entry.commit.message)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                let row = Row::new(vec![
+                    String::from(entry.command.name()),
+                    entry.commit.short.clone(),
+                    String::from(if entry.message.is_some() { "✎" } else { "" }),
+                    subject,
+                ]);
+                if entry.command.eq(&Command::Drop) {
+                    row.style(app.theme.inactive())
+                } else {
+                    row
+                }
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(7),
+                Constraint::Length(9),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .highlight_style(app.theme.selection());
+        let mut state = TableState::default()
+            .with_selected((!self.entries.is_empty()).then_some(self.selected));
+        f.render_stateful_widget(table, list, &mut state);
+        if let Some(text) = &self.notice {
+            f.render_widget(Paragraph::new(text.as_str()), notice);
+        }
+        if let Some(prompt) = &self.prompt {
+            prompt.render(f, &app.theme);
+        }
+        if let Some(confirm) = &self.confirm {
+            confirm.render(f, &app.theme);
+        }
+        if let Some(message) = &self.message {
+            render_message(f, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(hash: &str, command: Command, message: Option<&str>) -> Entry {
+        Entry {
+            commit: Commit {
+                hash: hash.to_string(),
+                short: hash.to_string(),
+                message: format!("Subject {hash}\n\nBody"),
+            },
+            command,
+            message: message.map(String::from),
+        }
+    }
+
+    #[test]
+    fn todo() {
+        let rebase = Rebase {
+            base: String::from("main"),
+            entries: vec![
+                entry("a", Command::Pick, Some("Squashed")),
+                entry("b", Command::Squash, None),
+                entry("c", Command::Drop, None),
+                entry("d", Command::Reword, Some("Reworded")),
+                entry("e", Command::Reword, None),
+                entry("f", Command::Pick, None),
+                entry("g", Command::Squash, None),
+            ],
+            selected: 0,
+            writing: None,
+            prompt: None,
+            confirm: None,
+            notice: None,
+            message: None,
+        };
+        let mut paths = Vec::new();
+        let todo = rebase.todo(&mut paths).unwrap();
+        let amend = |i: usize| {
+            format!(
+                "exec git commit --amend --only --no-verify --cleanup=verbatim --file {0} && rm -f {0}",
+                external::quote(&paths[i])
+            )
+        };
+        let expected = [
+            String::from("pick a Subject a"),
+            String::from("fixup b Subject b"),
+            String::from("drop c Subject c"),
+            amend(0),
+            String::from("pick d Subject d"),
+            amend(1),
+            String::from("reword e Subject e"),
+            String::from("pick f Subject f"),
+            String::from("squash g Subject g"),
+        ];
+        assert_eq!(todo, expected.join("\n") + "\n");
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "Squashed");
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "Reworded");
+        for path in paths {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use crate::complete::PEOPLE;
+use crate::limit::{BODY, SUBJECT};
+use crate::message::{self, Trailers};
+use crate::requirement::Requirement::{Optional, Required};
+use crate::{centered, external, git, render_message, set_text, App, Page};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Alignment;
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph};
+use ratatui::{Frame, Terminal};
+use std::io::Stdout;
+use tui_textarea::TextArea;
+
+///
+/// A release field and the commit fields gathered in it.
+///
+struct Source {
+    page: usize,
+    field: usize,
+    from: &'static [(usize, usize)],
+}
+
+const fn source(page: usize, field: usize, from: &'static [(usize, usize)]) -> Source {
+    Source { page, field, from }
+}
+
+const SOURCES: [Source; 6] = [
+    source(0, 2, &[(4, 0)]),
+    source(0, 3, &[(2, 0), (2, 3)]),
+    source(1, 0, &[(5, 0), (5, 2)]),
+    source(1, 1, &[(4, 1), (4, 2)]),
+    source(1, 2, &[(4, 3)]),
+    source(1, 3, &[PEOPLE[0]]),
+];
+
+pub enum ReleaseAction {
+    Stay,
+    Back,
+}
+
+///
+/// The annotated tag of a release, its notes are written in the form from the
+/// commits since the previous tag.
+///
+pub struct Release {
+    since: Option<String>,
+    ///
+    /// The name and the message of the tag, while the signing is asked.
+    ///
+    tag: Option<(String, String)>,
+    sign: bool,
+    format: &'static str,
+    message: Option<String>,
+    done: bool,
+}
+
+fn new_pages() -> [Page; 2] {
+    [
+        Page {
+            main_title: "Release",
+            areas: [
+                TextArea::default(),
+                TextArea::default(),
+                TextArea::default(),
+                TextArea::default(),
+            ],
+            limits: [SUBJECT, BODY, BODY, BODY],
+            requirements: [Required, Optional, Optional, Optional],
+            titles: ["Tag", "Changes", "Breaking changes", "Security"],
+            describe: [
+                "Indicate the name of the tag",
+                "List the changes of the release",
+                "Describe the breaking changes",
+                "Describe the security fixes and risks",
+            ],
+        },
+        Page {
+            main_title: "Upgrade",
+            areas: [
+                TextArea::default(),
+                TextArea::default(),
+                TextArea::default(),
+                TextArea::default(),
+            ],
+            limits: [BODY; 4],
+            requirements: [Optional; 4],
+            titles: ["Migrations", "Dependencies", "Rollback", "Contributors"],
+            describe: [
+                "Describe the migrations to run",
+                "Indicate the new needed dependencies and packages",
+                "Describe the rollback process",
+                "Thank the authors of the release",
+            ],
+        },
+    ]
+}
+
+///
+/// Fill the release fields from the commit messages, oldest first.
+///
+fn gather(pages: &mut [Page; 2], messages: &[String], trailers: &Trailers) {
+    let mut changes: Vec<String> = Vec::new();
+    let mut blocks: Vec<Vec<String>> = vec![Vec::new(); SOURCES.len()];
+    for text in messages {
+        let fields = message::parse(text, trailers);
+        let title = text.lines().next().unwrap_or_default().trim();
+        changes.push(format!("- {title}"));
+        for (i, source) in SOURCES.iter().enumerate() {
+            for (p, w, content) in &fields {
+                if !source.from.contains(&(*p, *w)) {
+                    continue;
+                }
+                if (*p, *w).eq(&PEOPLE[0]) {
+                    for author in content.lines().map(str::trim) {
+                        if !author.is_empty() && !blocks[i].iter().any(|a| a.eq(author)) {
+                            blocks[i].push(author.to_string());
+                        }
+                    }
+                } else {
+                    blocks[i].push(format!("{title}:\n{content}"));
+                }
+            }
+        }
+    }
+    set_text(&mut pages[0].areas[1], &changes.join("\n"));
+    for (source, block) in SOURCES.iter().zip(blocks) {
+        let separator = if source.from.eq(&[PEOPLE[0]]) {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        set_text(
+            &mut pages[source.page].areas[source.field],
+            &block.join(separator),
+        );
+    }
+}
+
+fn text(pages: &[Page], page: usize, field: usize) -> String {
+    pages[page].areas[field]
+        .lines()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+///
+/// The message of the tag named by the first field, the empty fields are left
+/// out.
+///
+pub fn notes(pages: &[Page]) -> String {
+    let mut blocks = vec![format!("Release {}", text(pages, 0, 0))];
+    for (p, page) in pages.iter().enumerate() {
+        for (w, title) in page.titles.iter().enumerate().skip(usize::from(p.eq(&0))) {
+            let content = text(pages, p, w);
+            if !content.is_empty() {
+                blocks.push(String::from(*title));
+                blocks.push(content);
+            }
+        }
+    }
+    blocks.join("\n\n") + "\n"
+}
+
+impl Release {
+    pub fn new() -> Self {
+        Self {
+            since: git::last_tag(),
+            tag: None,
+            sign: false,
+            format: git::signing_format(),
+            message: None,
+            done: false,
+        }
+    }
+
+    ///
+    /// The pages of the notes, filled from the commits since the previous tag.
+    ///
+    pub fn pages(&self, trailers: &Trailers) -> Vec<Page> {
+        let mut pages = new_pages();
+        gather(
+            &mut pages,
+            &git::messages_since(self.since.as_deref()),
+            trailers,
+        );
+        Vec::from(pages)
+    }
+
+    ///
+    /// Ask whether to sign the tag `name` before tagging.
+    ///
+    pub fn ask(&mut self, name: String, message: String) {
+        self.sign = git::signs_tags();
+        self.tag = Some((name, message));
+    }
+
+    ///
+    /// Whether a popup of the release takes the keys instead of the form.
+    ///
+    pub const fn is_open(&self) -> bool {
+        self.tag.is_some() || self.message.is_some()
+    }
+
+    fn tag(&mut self, rei: &mut Terminal<CrosstermBackend<Stdout>>, name: &str, message: &str) {
+        let sign = self.sign;
+        let result = if sign {
+            external::outside(rei, || git::tag(name, message, sign))
+        } else {
+            git::tag(name, message, sign)
+        };
+        match result {
+            Ok(output) => {
+                self.done = true;
+                self.message = Some(if output.is_empty() {
+                    format!("Tagged {name}.")
+                } else {
+                    output
+                });
+            }
+            Err(output) => self.message = Some(output),
+        }
+    }
+
+    pub fn handle(
+        &mut self,
+        rei: &mut Terminal<CrosstermBackend<Stdout>>,
+        key: KeyEvent,
+    ) -> ReleaseAction {
+        if self.message.take().is_some() {
+            return if self.done {
+                ReleaseAction::Back
+            } else {
+                ReleaseAction::Stay
+            };
+        }
+        match key.code {
+            KeyCode::Esc => self.tag = None,
+            KeyCode::Char(' ') => self.sign = !self.sign,
+            KeyCode::Enter => {
+                if let Some((name, message)) = self.tag.take() {
+                    self.tag(rei, &name, &message);
+                }
+            }
+            _ => {}
+        }
+        ReleaseAction::Stay
+    }
+
+    ///
+    /// Draw the popups of the release over the form.
+    ///
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        if let Some((name, _)) = &self.tag {
+            let since = self
+                .since
+                .as_deref()
+                .map_or_else(String::new, |tag| format!(", the notes start at {tag}"));
+            let area = centered(f.area(), 60, 6);
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(format!(
+                    "{} Sign the tag with the {} key\n{name}{since}",
+                    if self.sign { "[x]" } else { "[ ]" },
+                    self.format
+                ))
+                .style(app.theme.text())
+                .block(
+                    Block::default()
+                        .borders(Borders::all())
+                        .border_type(BorderType::Rounded)
+                        .padding(Padding::uniform(1))
+                        .title(" Tag ")
+                        .title_alignment(Alignment::Center)
+                        .title_bottom(" space toggle  ⏎ tag  esc cancel ")
+                        .title_alignment(Alignment::Center),
+                ),
+                area,
+            );
+        }
+        if let Some(message) = &self.message {
+            render_message(f, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn gathered_notes() {
+        let trailers = Trailers::new(&Config::default()).unwrap();
+        let messages = [
+            String::from("Add the parser\n\nBreaking\n\nThe API changed.\n\nCo-authored-by: Ann <ann@example.com>\n"),
+            String::from("Fix the lexer\n\nCo-authored-by: Ann <ann@example.com>\n"),
+        ];
+        let mut pages = new_pages();
+        gather(&mut pages, &messages, &trailers);
+        set_text(&mut pages[0].areas[0], "v1.0.0");
+        assert_eq!(
+            notes(&pages),
+            "Release v1.0.0\n\nChanges\n\n- Add the parser\n- Fix the lexer\n\nBreaking changes\n\nAdd the parser:\nThe API changed.\n\nContributors\n\nAnn <ann@example.com>\n"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::prompt::{Picker, PickerAction};
+use crate::theme::Theme;
+use crate::Page;
+use crossterm::event::KeyEvent;
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+
+///
+/// Whether a field must be filled before committing.
+///
+#[derive(Copy, Clone)]
+pub enum Requirement {
+    Optional,
+    Required,
+    ///
+    /// Required when the field at `(page, field)` is not empty.
+    ///
+    When(usize, usize),
+}
+
+fn is_empty(page: &Page, field: usize) -> bool {
+    page.areas[field]
+        .lines()
+        .iter()
+        .all(|line| line.trim().is_empty())
+}
+
+impl Requirement {
+    pub fn is_required(self, pages: &[Page]) -> bool {
+        match self {
+            Self::Optional => false,
+            Self::Required => true,
+            Self::When(page, field) => !is_empty(&pages[page], field),
+        }
+    }
+}
+
+///
+/// A required field left empty.
+///
+pub struct Unmet {
+    pub page: usize,
+    pub witch: usize,
+    pub reason: String,
+}
+
+///
+/// The requirements of the `visible` pages left unmet.
+///
+pub fn unmet(pages: &[Page], visible: &[usize]) -> Vec<Unmet> {
+    let mut missing: Vec<Unmet> = Vec::new();
+    for (p, page) in pages.iter().enumerate() {
+        if !visible.contains(&p) {
+            continue;
+        }
+        for (i, requirement) in page.requirements.iter().enumerate() {
+            if !requirement.is_required(pages) || !is_empty(page, i) {
+                continue;
+            }
+            let reason = match requirement {
+                Requirement::When(other, field) => format!(
+                    "{} / {} is required when {} is filled",
+                    page.main_title, page.titles[i], pages[*other].titles[*field]
+                ),
+                _ => format!("{} / {} is required", page.main_title, page.titles[i]),
+            };
+            missing.push(Unmet {
+                page: p,
+                witch: i,
+                reason,
+            });
+        }
+    }
+    missing
+}
+
+pub enum RequirementsAction {
+    Stay,
+    Close,
+    Jump(usize, usize),
+}
+
+///
+/// The list of the unmet requirements shown instead of committing.
+///
+pub struct Requirements {
+    unmet: Vec<Unmet>,
+    picker: Picker,
+}
+
+impl Requirements {
+    pub fn new(unmet: Vec<Unmet>) -> Self {
+        Self {
+            unmet,
+            picker: Picker::new("Can't commit yet", " ⏎ go to the field  esc close ", 80),
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> RequirementsAction {
+        match self.picker.handle(key, self.unmet.len()) {
+            PickerAction::Stay => RequirementsAction::Stay,
+            PickerAction::Close => RequirementsAction::Close,
+            PickerAction::Choose(i) => {
+                RequirementsAction::Jump(self.unmet[i].page, self.unmet[i].witch)
+            }
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let items: Vec<ListItem> = self
+            .unmet
+            .iter()
+            .map(|unmet| ListItem::new(format!("⚠ {}", unmet.reason)))
+            .collect();
+        self.picker.render(f, theme, items);
+    }
+}
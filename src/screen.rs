@@ -0,0 +1,343 @@
+use crate::branch::{Branches, BranchesAction};
+use crate::conflict::{Conflicts, ConflictsAction};
+use crate::fixup::{Fixup, FixupAction};
+use crate::form::{Form, FormAction};
+use crate::home::{Home, HomeAction, Item};
+use crate::keymap::Action;
+use crate::rebase::{Rebase, RebaseAction};
+use crate::release::{Release, ReleaseAction};
+use crate::stash::{Stashes, StashesAction};
+use crate::{git, message, profile, App};
+use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Alignment;
+use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use std::io::Stdout;
+
+///
+/// The commit message of the form as it will be committed.
+///
+pub struct Preview<'a> {
+    form: Box<Form<'a>>,
+    text: String,
+    scroll: u16,
+}
+
+///
+/// The result of the last commit, the form behind is already empty.
+///
+pub struct Committed<'a> {
+    form: Box<Form<'a>>,
+    text: String,
+}
+
+///
+/// Lines given by git, like the log or the status.
+///
+pub struct Listing {
+    title: &'static str,
+    lines: Vec<String>,
+    selected: usize,
+}
+
+pub enum Screen<'a> {
+    Home(Home),
+    Form(Box<Form<'a>>),
+    Preview(Preview<'a>),
+    Committed(Committed<'a>),
+    Listing(Listing),
+    Branches(Branches),
+    ///
+    /// The stashes, over the form they were opened from.
+    ///
+    Stashes(Stashes, Option<Box<Form<'a>>>),
+    ///
+    /// The notes of a release written in the form, then tagged.
+    ///
+    Release(Box<Form<'a>>, Box<Release>),
+    Conflicts(Conflicts),
+    Rebase(Box<Rebase>),
+    ///
+    /// The message of a commit of the rebase written in the form.
+    ///
+    Reword(Box<Form<'a>>, Box<Rebase>),
+    Fixup(Fixup),
+}
+
+fn text(f: &mut Frame, app: &App, title: &str, text: &str, bottom: &str, scroll: u16) {
+    f.render_widget(
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .style(app.theme.text())
+            .block(
+                Block::default()
+                    .borders(Borders::all())
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" {title} "))
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(format!(" {bottom} "))
+                    .title_alignment(Alignment::Center),
+            ),
+        f.area(),
+    );
+}
+
+impl Listing {
+    const fn new(title: &'static str, lines: Vec<String>) -> Self {
+        Self {
+            title,
+            lines,
+            selected: 0,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, app: &App) {
+        let items: Vec<ListItem> = self
+            .lines
+            .iter()
+            .map(|line| ListItem::new(line.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::all())
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" {} ", self.title))
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(" ↑↓ move  esc back ")
+                    .title_alignment(Alignment::Center),
+            )
+            .style(app.theme.text())
+            .highlight_style(app.theme.selection());
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        f.render_stateful_widget(list, f.area(), &mut state);
+    }
+
+    fn handle<'a>(mut self, key: KeyEvent) -> Screen<'a> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Screen::Home(Home::new()),
+            KeyCode::Down | KeyCode::Char('j')
+                if self.selected.lt(&(self.lines.len().saturating_sub(1))) =>
+            {
+                self.selected += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            _ => {}
+        }
+        Screen::Listing(self)
+    }
+}
+
+impl<'a> Screen<'a> {
+    fn render(&mut self, f: &mut Frame, app: &App) {
+        match self {
+            Self::Home(home) => home.render(f, app),
+            Self::Form(form) => form.render(f, app),
+            Self::Preview(preview) => text(
+                f,
+                app,
+                "Preview",
+                &preview.text,
+                &format!(
+                    "↑↓ scroll  {} commit  esc back",
+                    app.keymap.keys(Action::Commit)
+                ),
+                preview.scroll,
+            ),
+            Self::Committed(committed) => text(
+                f,
+                app,
+                "Committed",
+                &committed.text,
+                "press any key to write the next commit",
+                0,
+            ),
+            Self::Listing(listing) => listing.render(f, app),
+            Self::Branches(branches) => branches.render(f, app),
+            Self::Stashes(stashes, _) => stashes.render(f, app),
+            Self::Release(form, release) => {
+                form.render(f, app);
+                release.render(f, app);
+            }
+            Self::Conflicts(conflicts) => conflicts.render(f, app),
+            Self::Rebase(rebase) => rebase.render(f, app),
+            Self::Reword(form, _) => form.render(f, app),
+            Self::Fixup(fixup) => fixup.render(f, app),
+        }
+    }
+
+    ///
+    /// The screen after the event, `None` to quit rei.
+    ///
+    fn handle(
+        self,
+        rei: &mut Terminal<CrosstermBackend<Stdout>>,
+        app: &'a App,
+        event: Event,
+    ) -> Option<Self> {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                let area = rei.get_frame().area();
+                return Some(match self {
+                    Self::Form(mut form) => {
+                        form.handle_mouse(mouse, area, app);
+                        Self::Form(form)
+                    }
+                    Self::Reword(mut form, rebase) => {
+                        form.handle_mouse(mouse, area, app);
+                        Self::Reword(form, rebase)
+                    }
+                    Self::Release(mut form, release) => {
+                        if !release.is_open() {
+                            form.handle_mouse(mouse, area, app);
+                        }
+                        Self::Release(form, release)
+                    }
+                    screen => screen,
+                });
+            }
+            _ => return Some(self),
+        };
+        Some(match self {
+            Self::Home(mut home) => match home.handle(app, key) {
+                HomeAction::Stay | HomeAction::Open(Item::Commit) => Self::Home(home),
+                HomeAction::Quit => return None,
+                HomeAction::Form(profile) => {
+                    Self::Form(Box::new(Form::new(app, &app.profiles[profile])))
+                }
+                HomeAction::Open(Item::Branches) => Self::Branches(Branches::new()),
+                HomeAction::Open(Item::Stash) => Self::Stashes(Stashes::new(String::new()), None),
+                HomeAction::Open(Item::Release) => {
+                    let release = Release::new();
+                    let form = Form::releasing(app, release.pages(&app.trailers));
+                    Self::Release(Box::new(form), Box::new(release))
+                }
+                HomeAction::Open(Item::Conflicts) => Self::Conflicts(Conflicts::new()),
+                HomeAction::Open(Item::Rebase) => Self::Rebase(Box::new(Rebase::new())),
+                HomeAction::Open(Item::Fixup) => Self::Fixup(Fixup::new()),
+                HomeAction::Open(Item::Log) => Self::Listing(Listing::new("Log", git::log(500))),
+                HomeAction::Open(Item::Status) => {
+                    Self::Listing(Listing::new("Status", git::status()))
+                }
+            },
+            Self::Form(mut form) => match form.handle_key(rei, app, key) {
+                FormAction::Stay | FormAction::Written(_) => Self::Form(form),
+                FormAction::Quit => Self::Home(Home::new()),
+                FormAction::Preview => {
+                    let text = form.message(app);
+                    Self::Preview(Preview {
+                        form,
+                        text,
+                        scroll: 0,
+                    })
+                }
+                FormAction::Stash(title) => Self::Stashes(Stashes::new(title), Some(form)),
+                FormAction::Committed(output) => Self::Committed(Committed {
+                    form,
+                    text: format!("{output}\n\nSignature: {}", git::signature()),
+                }),
+            },
+            Self::Preview(mut preview) => match (app.keymap.action(key), key.code) {
+                (Some(Action::Commit), _) => {
+                    preview.form.ask_commit(app);
+                    Self::Form(preview.form)
+                }
+                (Some(Action::Quit | Action::Preview), _) | (_, KeyCode::Esc) => {
+                    Self::Form(preview.form)
+                }
+                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                    preview.scroll = preview.scroll.saturating_add(1);
+                    Self::Preview(preview)
+                }
+                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                    preview.scroll = preview.scroll.saturating_sub(1);
+                    Self::Preview(preview)
+                }
+                _ => Self::Preview(preview),
+            },
+            Self::Committed(committed) => match app.keymap.action(key) {
+                Some(Action::Quit) => Self::Home(Home::new()),
+                _ => Self::Form(committed.form),
+            },
+            Self::Listing(listing) => listing.handle(key),
+            Self::Branches(mut branches) => match branches.handle(key) {
+                BranchesAction::Stay => Self::Branches(branches),
+                BranchesAction::Back => Self::Home(Home::new()),
+            },
+            Self::Stashes(mut stashes, form) => match (stashes.handle(key), form) {
+                (StashesAction::Stay, form) => Self::Stashes(stashes, form),
+                (StashesAction::Back, Some(form)) => Self::Form(form),
+                (StashesAction::Back, None) => Self::Home(Home::new()),
+            },
+            Self::Release(form, mut release) if release.is_open() => {
+                match release.handle(rei, key) {
+                    ReleaseAction::Stay => Self::Release(form, release),
+                    ReleaseAction::Back => Self::Home(Home::new()),
+                }
+            }
+            Self::Release(mut form, mut release) => match form.handle_key(rei, app, key) {
+                FormAction::Written(message) => {
+                    release.ask(form.title(), message);
+                    Self::Release(form, release)
+                }
+                FormAction::Quit => Self::Home(Home::new()),
+                _ => Self::Release(form, release),
+            },
+            Self::Conflicts(mut conflicts) => match conflicts.handle(rei, key) {
+                ConflictsAction::Stay => Self::Conflicts(conflicts),
+                ConflictsAction::Back => Self::Home(Home::new()),
+                ConflictsAction::Form(message) => {
+                    let mut form = Form::new(app, &app.profiles[app.profile]);
+                    form.fill(&message::parse(&message, &app.trailers));
+                    Self::Form(Box::new(form))
+                }
+            },
+            Self::Rebase(mut rebase) => match rebase.handle(app, key) {
+                RebaseAction::Stay => Self::Rebase(rebase),
+                RebaseAction::Back => Self::Home(Home::new()),
+                RebaseAction::Reword(message) => {
+                    let profile = profile::covering(&app.profiles, app.profile, &message.fields);
+                    Self::Reword(Box::new(Form::rewording(app, profile, &message)), rebase)
+                }
+            },
+            Self::Reword(mut form, mut rebase) => match form.handle_key(rei, app, key) {
+                FormAction::Written(message) => {
+                    rebase.written(Some(message));
+                    Self::Rebase(rebase)
+                }
+                FormAction::Quit => {
+                    rebase.written(None);
+                    Self::Rebase(rebase)
+                }
+                _ => Self::Reword(form, rebase),
+            },
+            Self::Fixup(mut fixup) => match fixup.handle(key) {
+                FixupAction::Stay => Self::Fixup(fixup),
+                FixupAction::Back => Self::Home(Home::new()),
+            },
+        })
+    }
+}
+
+///
+/// The single event loop of rei, each event moves the current screen to the
+/// next one.
+///
+pub fn run(rei: &mut Terminal<CrosstermBackend<Stdout>>, app: &App) {
+    let mut screen = Screen::Home(Home::new());
+    loop {
+        if rei.draw(|f| screen.render(f, app)).is_err() {
+            break;
+        }
+        let Ok(event) = event::read() else {
+            break;
+        };
+        match screen.handle(rei, app, event) {
+            Some(next) => screen = next,
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::theme::Theme;
+use crate::{set_text, Page};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph};
+use ratatui::Frame;
+use regex::Regex;
+use tui_textarea::CursorMove;
+
+///
+/// A match of the pattern, the columns are byte offsets in the line.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Match {
+    page: usize,
+    field: usize,
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+pub enum SearchAction {
+    Stay,
+    Close,
+    Focus(usize, usize),
+}
+
+///
+/// The search and replace prompt over the fields of the visible pages.
+///
+pub struct Search {
+    pattern: String,
+    replacement: String,
+    replacing: bool,
+    regex: bool,
+    matches: Vec<Match>,
+    current: usize,
+    status: String,
+}
+
+impl Search {
+    pub const fn new() -> Self {
+        Self {
+            pattern: String::new(),
+            replacement: String::new(),
+            replacing: false,
+            regex: false,
+            matches: Vec::new(),
+            current: 0,
+            status: String::new(),
+        }
+    }
+
+    fn compile(&self) -> Result<Option<Regex>, String> {
+        if self.pattern.is_empty() {
+            return Ok(None);
+        }
+        let pattern = if self.regex {
+            self.pattern.clone()
+        } else {
+            regex::escape(&self.pattern)
+        };
+        Regex::new(&pattern).map(Some).map_err(|e| e.to_string())
+    }
+
+    ///
+    /// Find the matches again and highlight them in every field.
+    ///
+    fn find(&mut self, pages: &mut [Page], visible: &[usize]) {
+        self.matches.clear();
+        let regex = match self.compile() {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.status = e.lines().last().unwrap_or_default().to_string();
+                None
+            }
+        };
+        for &p in visible {
+            for (field, area) in pages[p].areas.iter_mut().enumerate() {
+                area.set_search_style(Style::default().add_modifier(Modifier::REVERSED));
+                let _ = area.set_search_pattern(regex.as_ref().map_or("", Regex::as_str));
+                let Some(regex) = regex.as_ref() else {
+                    continue;
+                };
+                for (row, line) in area.lines().iter().enumerate() {
+                    self.matches
+                        .extend(
+                            regex
+                                .find_iter(line)
+                                .filter(|m| !m.is_empty())
+                                .map(|m| Match {
+                                    page: p,
+                                    field,
+                                    row,
+                                    start: m.start(),
+                                    end: m.end(),
+                                }),
+                        );
+                }
+            }
+        }
+        if self.current.ge(&self.matches.len()) {
+            self.current = 0;
+        }
+        if regex.is_some() {
+            self.status = format!("{} matches", self.matches.len());
+        }
+    }
+
+    ///
+    /// Put the cursor of the field on the current match.
+    ///
+    fn jump(&self, pages: &mut [Page]) -> SearchAction {
+        let Some(m) = self.matches.get(self.current) else {
+            return SearchAction::Stay;
+        };
+        let area = &mut pages[m.page].areas[m.field];
+        let column = area.lines()[m.row][..m.start].chars().count();
+        area.move_cursor(CursorMove::Jump(
+            u16::try_from(m.row).unwrap_or(u16::MAX),
+            u16::try_from(column).unwrap_or(u16::MAX),
+        ));
+        SearchAction::Focus(m.page, m.field)
+    }
+
+    fn replace_one(&mut self, pages: &mut [Page], visible: &[usize]) {
+        let Ok(Some(regex)) = self.compile() else {
+            return;
+        };
+        let Some(m) = self.matches.get(self.current).copied() else {
+            return;
+        };
+        let area = &mut pages[m.page].areas[m.field];
+        let mut lines = area.lines().to_vec();
+        let line = &lines[m.row];
+        let mut replaced = String::new();
+        if !self.regex {
+            replaced.push_str(&self.replacement);
+        } else if let Some(captures) = regex.captures_at(line, m.start) {
+            captures.expand(&self.replacement, &mut replaced);
+        }
+        lines[m.row] = format!("{}{replaced}{}", &line[..m.start], &line[m.end..]);
+        set_text(area, &lines.join("\n"));
+        self.find(pages, visible);
+        self.status = format!("1 replaced, {} matches left", self.matches.len());
+    }
+
+    ///
+    /// Replace every found match, the empty ones are left alone.
+    ///
+    fn replace_all(&mut self, pages: &mut [Page], visible: &[usize]) {
+        let Ok(Some(regex)) = self.compile() else {
+            return;
+        };
+        let count = self.matches.len();
+        for field in self
+            .matches
+            .chunk_by(|a, b| (a.page, a.field).eq(&(b.page, b.field)))
+        {
+            let area = &mut pages[field[0].page].areas[field[0].field];
+            let mut lines = area.lines().to_vec();
+            for row in field.chunk_by(|a, b| a.row.eq(&b.row)) {
+                let line = &lines[row[0].row];
+                let mut replaced = String::new();
+                let mut last = 0;
+                for m in row {
+                    replaced.push_str(&line[last..m.start]);
+                    if !self.regex {
+                        replaced.push_str(&self.replacement);
+                    } else if let Some(captures) = regex.captures_at(line, m.start) {
+                        captures.expand(&self.replacement, &mut replaced);
+                    }
+                    last = m.end;
+                }
+                replaced.push_str(&line[last..]);
+                lines[row[0].row] = replaced;
+            }
+            set_text(area, &lines.join("\n"));
+        }
+        self.find(pages, visible);
+        self.status = format!("{count} replaced");
+    }
+
+    pub fn handle(&mut self, key: KeyEvent, pages: &mut [Page], visible: &[usize]) -> SearchAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.pattern.clear();
+                self.find(pages, visible);
+                return SearchAction::Close;
+            }
+            KeyCode::Enter | KeyCode::Down => {
+                if !self.matches.is_empty() {
+                    self.current = (self.current + 1) % self.matches.len();
+                }
+                return self.jump(pages);
+            }
+            KeyCode::Up => {
+                if !self.matches.is_empty() {
+                    self.current = self
+                        .current
+                        .checked_sub(1)
+                        .unwrap_or(self.matches.len() - 1);
+                }
+                return self.jump(pages);
+            }
+            KeyCode::Tab | KeyCode::BackTab => self.replacing = !self.replacing,
+            KeyCode::Char('r') if ctrl => {
+                self.regex = !self.regex;
+                self.find(pages, visible);
+            }
+            KeyCode::Char('o') if ctrl => {
+                self.replace_one(pages, visible);
+                return self.jump(pages);
+            }
+            KeyCode::Char('a') if ctrl => self.replace_all(pages, visible),
+            KeyCode::Backspace => {
+                if self.replacing {
+                    self.replacement.pop();
+                } else {
+                    self.pattern.pop();
+                    self.current = 0;
+                    self.find(pages, visible);
+                    return self.jump(pages);
+                }
+            }
+            KeyCode::Char(c) if !ctrl => {
+                if self.replacing {
+                    self.replacement.push(c);
+                } else {
+                    self.pattern.push(c);
+                    self.current = 0;
+                    self.find(pages, visible);
+                    return self.jump(pages);
+                }
+            }
+            _ => {}
+        }
+        SearchAction::Stay
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let screen = f.area();
+        let width = screen.width.min(80);
+        let height = screen.height.min(4);
+        let area = Rect::new(
+            screen.x + (screen.width - width) / 2,
+            screen.y + screen.height.saturating_sub(height + 1),
+            width,
+            height,
+        );
+        let cursor = |on: bool| if on { "▏" } else { "" };
+        let position = if self.matches.is_empty() {
+            self.status.clone()
+        } else {
+            format!(
+                "{}/{} — {}",
+                self.current + 1,
+                self.matches.len(),
+                self.status
+            )
+        };
+        let text = vec![
+            Line::from(format!(
+                "{} {}{}",
+                if self.regex { "Regex:  " } else { "Find:   " },
+                self.pattern,
+                cursor(!self.replacing)
+            )),
+            Line::from(format!(
+                "Replace: {}{}",
+                self.replacement,
+                cursor(self.replacing)
+            )),
+        ];
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(text).style(theme.text()).block(
+                Block::default()
+                    .borders(Borders::all())
+                    .border_type(BorderType::Rounded)
+                    .padding(Padding::horizontal(1))
+                    .title(format!(" Search {position} "))
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(" ⏎ next  ↑ back  tab replace  ^r regex  ^o one  ^a all  esc ")
+                    .title_alignment(Alignment::Center),
+            ),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::new_pages;
+
+    #[test]
+    fn replace_all_skips_empty_matches() {
+        let mut pages = new_pages();
+        set_text(&mut pages[0].areas[0], "xaax\nyay");
+        set_text(&mut pages[0].areas[1], "none");
+        let mut search = Search::new();
+        search.pattern = String::from("(a+)");
+        search.replacement = String::from("[$1]");
+        search.regex = true;
+        search.find(&mut pages, &[0]);
+        search.replace_all(&mut pages, &[0]);
+        assert_eq!(pages[0].areas[0].lines(), ["x[aa]x", "y[a]y"]);
+        assert_eq!(pages[0].areas[1].lines(), ["none"]);
+        search.pattern = String::from("y*");
+        search.replacement = String::from("-");
+        search.find(&mut pages, &[0]);
+        search.replace_all(&mut pages, &[0]);
+        assert_eq!(pages[0].areas[0].lines(), ["x[aa]x", "-[a]-"]);
+    }
+}
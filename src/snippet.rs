@@ -0,0 +1,94 @@
+use crate::field_position;
+use crate::prompt::{Picker, PickerAction};
+use crate::theme::Theme;
+use crossterm::event::KeyEvent;
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+use serde::Deserialize;
+
+///
+/// A reusable text, offered for the listed fields or for all of them.
+///
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Snippet {
+    pub name: String,
+    pub text: String,
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+impl Snippet {
+    ///
+    /// # Errors
+    ///
+    /// When a field of the snippet does not exist.
+    ///
+    pub fn check(&self) -> Result<(), String> {
+        for field in &self.fields {
+            if field_position(field).is_none() {
+                return Err(format!("snippet `{}`: unknown field `{field}`", self.name));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn applies(&self, title: &str) -> bool {
+        self.fields.is_empty()
+            || self
+                .fields
+                .iter()
+                .any(|field| field.eq_ignore_ascii_case(title))
+    }
+}
+
+pub enum SnippetAction {
+    Stay,
+    Close,
+    Insert(String),
+}
+
+///
+/// The choice of the snippet to insert in the focused field.
+///
+pub struct SnippetPicker {
+    snippets: Vec<Snippet>,
+    picker: Picker,
+}
+
+impl SnippetPicker {
+    pub fn new(snippets: &[Snippet], title: &str) -> Self {
+        Self {
+            snippets: snippets
+                .iter()
+                .filter(|snippet| snippet.applies(title))
+                .cloned()
+                .collect(),
+            picker: Picker::new("Snippets", " ⏎ insert  esc close ", 80),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snippets.is_empty()
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> SnippetAction {
+        match self.picker.handle(key, self.snippets.len()) {
+            PickerAction::Stay => SnippetAction::Stay,
+            PickerAction::Close => SnippetAction::Close,
+            PickerAction::Choose(i) => SnippetAction::Insert(self.snippets[i].text.clone()),
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let items: Vec<ListItem> = self
+            .snippets
+            .iter()
+            .map(|snippet| {
+                let preview = snippet.text.lines().next().unwrap_or_default();
+                ListItem::new(format!("{:<16} {preview}", snippet.name))
+            })
+            .collect();
+        self.picker.render(f, theme, items);
+    }
+}
@@ -0,0 +1,730 @@
+use crate::limit::{is_fence, Limit};
+use crate::prompt::{Picker, PickerAction};
+use crate::theme::{Length, Theme};
+use crate::{git, set_text};
+use crossterm::event::KeyEvent;
+use ratatui::style::Modifier;
+use ratatui::widgets::ListItem;
+use ratatui::Frame;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tui_textarea::{CursorMove, TextArea};
+
+///
+/// The dictionary of the language the binary is built for.
+///
+const LANGUAGE: &str = if cfg!(feature = "fr") {
+    "fr_FR"
+} else if cfg!(feature = "es") {
+    "es_ES"
+} else if cfg!(feature = "it") {
+    "it_IT"
+} else {
+    "en_US"
+};
+
+///
+/// The words accepted in a repository, one per line.
+///
+const WORDS: &str = ".rei-words";
+
+type Flag = u32;
+
+#[derive(Copy, Clone)]
+enum FlagFormat {
+    Char,
+    Long,
+    Num,
+}
+
+impl FlagFormat {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            Self::Char => flags.chars().map(u32::from).collect(),
+            Self::Long => flags
+                .chars()
+                .collect::<Vec<char>>()
+                .chunks(2)
+                .map(|pair| pair.iter().fold(0, |flag, c| (flag << 16) | u32::from(*c)))
+                .collect(),
+            Self::Num => flags
+                .split(',')
+                .filter_map(|flag| flag.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
+///
+/// A character of an affix condition.
+///
+enum Condition {
+    Any,
+    Is(char),
+    In(Vec<char>),
+    NotIn(Vec<char>),
+}
+
+impl Condition {
+    fn parse(condition: &str) -> Vec<Self> {
+        let mut conditions: Vec<Self> = Vec::new();
+        let mut chars = condition.chars();
+        while let Some(c) = chars.next() {
+            conditions.push(match c {
+                '.' => Self::Any,
+                '[' => {
+                    let set: String = chars.by_ref().take_while(|c| c.ne(&']')).collect();
+                    match set.strip_prefix('^') {
+                        Some(set) => Self::NotIn(set.chars().collect()),
+                        None => Self::In(set.chars().collect()),
+                    }
+                }
+                c => Self::Is(c),
+            });
+        }
+        conditions
+    }
+
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Is(expected) => c.eq(expected),
+            Self::In(set) => set.contains(&c),
+            Self::NotIn(set) => !set.contains(&c),
+        }
+    }
+}
+
+///
+/// A prefix or suffix rule of the `.aff` file.
+///
+struct Affix {
+    flag: Flag,
+    cross: bool,
+    strip: String,
+    add: String,
+    condition: Vec<Condition>,
+}
+
+impl Affix {
+    fn prefix_applies(&self, stem: &str) -> bool {
+        let chars: Vec<char> = stem.chars().collect();
+        chars.len().ge(&self.condition.len())
+            && self
+                .condition
+                .iter()
+                .zip(&chars)
+                .all(|(condition, c)| condition.matches(*c))
+    }
+
+    fn suffix_applies(&self, stem: &str) -> bool {
+        let chars: Vec<char> = stem.chars().collect();
+        chars.len().ge(&self.condition.len())
+            && self
+                .condition
+                .iter()
+                .rev()
+                .zip(chars.iter().rev())
+                .all(|(condition, c)| condition.matches(*c))
+    }
+
+    ///
+    /// The stem of `word` with this prefix removed.
+    ///
+    fn unprefix(&self, word: &str) -> Option<String> {
+        let rest = word.strip_prefix(self.add.as_str())?;
+        let stem = format!("{}{rest}", self.strip);
+        (!stem.is_empty() && self.prefix_applies(&stem)).then_some(stem)
+    }
+
+    ///
+    /// The stem of `word` with this suffix removed.
+    ///
+    fn unsuffix(&self, word: &str) -> Option<String> {
+        let rest = word.strip_suffix(self.add.as_str())?;
+        let stem = format!("{rest}{}", self.strip);
+        (!stem.is_empty() && self.suffix_applies(&stem)).then_some(stem)
+    }
+}
+
+///
+/// A Hunspell dictionary, made of the `.dic` stems and the `.aff` rules.
+///
+/// Compounds and the continuation classes of the affixes are not supported,
+/// the words they give can be added to the repository word list.
+///
+pub struct Dictionary {
+    stems: HashMap<String, Vec<Flag>>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+    need_affix: Option<Flag>,
+    forbidden: Option<Flag>,
+    try_chars: Vec<char>,
+}
+
+///
+/// The content of a dictionary file, in the encoding given by `SET`.
+///
+fn decode(bytes: &[u8], utf8: bool) -> String {
+    if utf8 {
+        String::from_utf8_lossy(bytes).to_string()
+    } else {
+        bytes.iter().map(|byte| char::from(*byte)).collect()
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+///
+/// The directories searched for the dictionaries.
+///
+fn directories() -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = Vec::new();
+    if let Some(data) = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    {
+        directories.push(data.join("rei").join("dict"));
+    }
+    for dir in [
+        "/usr/share/hunspell",
+        "/usr/share/myspell",
+        "/usr/share/myspell/dicts",
+        "/usr/local/share/hunspell",
+        "/opt/homebrew/share/hunspell",
+    ] {
+        directories.push(PathBuf::from(dir));
+    }
+    directories
+}
+
+impl Dictionary {
+    ///
+    /// The dictionary at `path`, without extension, or the one of the
+    /// language found in the usual directories. `None` when there is none.
+    ///
+    /// # Errors
+    ///
+    /// On unreadable dictionary.
+    ///
+    pub fn find(path: Option<&str>) -> Result<Option<Self>, String> {
+        let base = match path {
+            Some(path) => PathBuf::from(path),
+            None => match directories()
+                .into_iter()
+                .map(|dir| dir.join(LANGUAGE))
+                .find(|base| base.with_extension("dic").exists())
+            {
+                Some(base) => base,
+                None => return Ok(None),
+            },
+        };
+        Self::load(&base.with_extension("aff"), &base.with_extension("dic")).map(Some)
+    }
+
+    fn load(aff: &Path, dic: &Path) -> Result<Self, String> {
+        let bytes = read(aff)?;
+        let utf8 = decode(&bytes, false)
+            .lines()
+            .find_map(|line| line.strip_prefix("SET "))
+            .is_none_or(|set| set.trim().eq_ignore_ascii_case("UTF-8"));
+        let mut dictionary = Self {
+            stems: HashMap::new(),
+            prefixes: Vec::new(),
+            suffixes: Vec::new(),
+            need_affix: None,
+            forbidden: None,
+            try_chars: Vec::new(),
+        };
+        let mut format = FlagFormat::Char;
+        let mut cross: HashMap<(bool, Flag), bool> = HashMap::new();
+        for line in decode(&bytes, utf8).lines() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["FLAG", "long", ..] => format = FlagFormat::Long,
+                ["FLAG", "num", ..] => format = FlagFormat::Num,
+                ["TRY", chars, ..] => dictionary.try_chars = chars.chars().collect(),
+                ["NEEDAFFIX", flag, ..] => {
+                    dictionary.need_affix = format.parse(flag).first().copied();
+                }
+                ["FORBIDDENWORD", flag, ..] => {
+                    dictionary.forbidden = format.parse(flag).first().copied();
+                }
+                [kind @ ("PFX" | "SFX"), flag, product, count]
+                    if count.chars().all(|c| c.is_ascii_digit()) =>
+                {
+                    if let Some(flag) = format.parse(flag).first() {
+                        cross.insert((kind.eq(&"PFX"), *flag), product.eq(&"Y"));
+                    }
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, condition, ..] => {
+                    let Some(flag) = format.parse(flag).first().copied() else {
+                        continue;
+                    };
+                    let prefix = kind.eq(&"PFX");
+                    let add = add.split('/').next().unwrap_or_default();
+                    let affix = Affix {
+                        flag,
+                        cross: cross.get(&(prefix, flag)).copied().unwrap_or(false),
+                        strip: if strip.eq(&"0") { "" } else { strip }.to_string(),
+                        add: if add.eq("0") { "" } else { add }.to_string(),
+                        condition: Condition::parse(condition),
+                    };
+                    if prefix {
+                        dictionary.prefixes.push(affix);
+                    } else {
+                        dictionary.suffixes.push(affix);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for line in decode(&read(dic)?, utf8).lines().skip(1) {
+            let entry = line.split_whitespace().next().unwrap_or_default();
+            if entry.is_empty() {
+                continue;
+            }
+            let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            dictionary
+                .stems
+                .entry(word.to_string())
+                .or_default()
+                .extend(format.parse(flags));
+        }
+        Ok(dictionary)
+    }
+
+    fn has(&self, stem: &str, flags: &[Flag]) -> bool {
+        self.stems.get(stem).is_some_and(|own| {
+            self.forbidden
+                .is_none_or(|forbidden| !own.contains(&forbidden))
+                && flags.iter().all(|flag| own.contains(flag))
+        })
+    }
+
+    fn known(&self, word: &str) -> bool {
+        if self
+            .stems
+            .get(word)
+            .is_some_and(|flags| self.need_affix.is_none_or(|need| !flags.contains(&need)))
+            && self.has(word, &[])
+        {
+            return true;
+        }
+        for suffix in &self.suffixes {
+            let Some(stem) = suffix.unsuffix(word) else {
+                continue;
+            };
+            if self.has(&stem, &[suffix.flag]) {
+                return true;
+            }
+            if suffix.cross
+                && self.prefixes.iter().any(|prefix| {
+                    prefix.cross
+                        && prefix
+                            .unprefix(&stem)
+                            .is_some_and(|root| self.has(&root, &[prefix.flag, suffix.flag]))
+                })
+            {
+                return true;
+            }
+        }
+        self.prefixes.iter().any(|prefix| {
+            prefix
+                .unprefix(word)
+                .is_some_and(|stem| self.has(&stem, &[prefix.flag]))
+        })
+    }
+
+    ///
+    /// Whether the word is correct, as written or in lower case when it is
+    /// capitalized or in upper case.
+    ///
+    pub fn check(&self, word: &str) -> bool {
+        if self.known(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        let mut chars = word.chars();
+        let capitalized = chars.next().is_some_and(char::is_uppercase);
+        capitalized && lower.ne(word) && (self.known(&lower) || self.known(&capitalize(&lower)))
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+///
+/// The number of edits between the two words.
+///
+fn distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca.ne(cb));
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+///
+/// A dictionary with the words of the repository and the verdicts already
+/// given.
+///
+pub struct Speller<'a> {
+    dictionary: &'a Dictionary,
+    words: HashSet<String>,
+    verdicts: HashMap<String, bool>,
+}
+
+impl<'a> Speller<'a> {
+    pub fn new(dictionary: &'a Dictionary) -> Self {
+        let words = git::toplevel()
+            .and_then(|root| fs::read_to_string(root.join(WORDS)).ok())
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(String::from)
+            .collect();
+        Self {
+            dictionary,
+            words,
+            verdicts: HashMap::new(),
+        }
+    }
+
+    pub fn check(&mut self, word: &str) -> bool {
+        if self.words.contains(word) || self.words.contains(&word.to_lowercase()) {
+            return true;
+        }
+        if let Some(verdict) = self.verdicts.get(word) {
+            return *verdict;
+        }
+        let verdict = self.dictionary.check(word);
+        self.verdicts.insert(word.to_string(), verdict);
+        verdict
+    }
+
+    ///
+    /// Add the word to the list of the repository.
+    ///
+    /// # Errors
+    ///
+    /// Outside a repository or when the list can't be written.
+    ///
+    pub fn add(&mut self, word: &str) -> Result<(), String> {
+        let path = git::toplevel()
+            .ok_or_else(|| String::from("not in a git repository"))?
+            .join(WORDS);
+        let mut content = fs::read_to_string(&path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(word);
+        content.push('\n');
+        fs::write(&path, content).map_err(|e| format!("{}: {e}", path.display()))?;
+        self.words.insert(word.to_string());
+        Ok(())
+    }
+
+    ///
+    /// The closest correct words, the single edits found with the `TRY`
+    /// characters first then the stems at two edits at most.
+    ///
+    pub fn suggest(&mut self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let mut candidates: Vec<String> = Vec::new();
+        let try_chars = self.dictionary.try_chars.clone();
+        for i in 0..=chars.len() {
+            let (head, tail) = chars.split_at(i);
+            let head: String = head.iter().collect();
+            if !tail.is_empty() {
+                candidates.push(format!("{head}{}", tail[1..].iter().collect::<String>()));
+            }
+            if tail.len().ge(&2) {
+                candidates.push(format!(
+                    "{head}{}{}{}",
+                    tail[1],
+                    tail[0],
+                    tail[2..].iter().collect::<String>()
+                ));
+            }
+            for c in &try_chars {
+                candidates.push(format!("{head}{c}{}", tail.iter().collect::<String>()));
+                if !tail.is_empty() {
+                    candidates.push(format!("{head}{c}{}", tail[1..].iter().collect::<String>()));
+                }
+            }
+        }
+        let mut suggestions: Vec<(usize, String)> = Vec::new();
+        for candidate in candidates {
+            if candidate.ne(word)
+                && !suggestions.iter().any(|(_, known)| known.eq(&candidate))
+                && self.check(&candidate)
+            {
+                suggestions.push((1, candidate));
+            }
+        }
+        for stem in self.dictionary.stems.keys() {
+            if stem.chars().count().abs_diff(chars.len()).gt(&2)
+                || suggestions.iter().any(|(_, known)| known.eq(stem))
+            {
+                continue;
+            }
+            let edits = distance(&chars, &stem.chars().collect::<Vec<char>>());
+            if edits.le(&2) {
+                suggestions.push((edits, stem.clone()));
+            }
+        }
+        suggestions.sort();
+        suggestions
+            .into_iter()
+            .take(8)
+            .map(|(_, word)| word)
+            .collect()
+    }
+
+    ///
+    /// Underline the misspelled words of a prose field, code blocks are
+    /// skipped.
+    ///
+    pub fn underline(&mut self, textarea: &mut TextArea<'_>, limit: Limit, theme: &Theme) {
+        if !limit.prose {
+            return;
+        }
+        let mut misspelled: Vec<String> = Vec::new();
+        for word in words(textarea.lines()) {
+            if !misspelled.contains(&word) && !self.check(&word) {
+                misspelled.push(word);
+            }
+        }
+        let pattern = if misspelled.is_empty() {
+            String::new()
+        } else {
+            let words: Vec<String> = misspelled.iter().map(|word| regex::escape(word)).collect();
+            format!(r"\b(?:{})\b", words.join("|"))
+        };
+        textarea.set_search_style(
+            theme
+                .length(Length::TooLong)
+                .add_modifier(Modifier::UNDERLINED),
+        );
+        let _ = textarea.set_search_pattern(pattern);
+    }
+}
+
+///
+/// The words of the prose, the code blocks, inline code, links and
+/// identifiers left out.
+///
+fn words(lines: &[String]) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut fenced = false;
+    for line in lines {
+        if is_fence(line) {
+            fenced = !fenced;
+            continue;
+        }
+        if fenced || line.starts_with("    ") || line.starts_with('\t') {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            if token.contains("://")
+                || token.contains(['`', '@', '/', '_', '<', '>'])
+                || token.chars().any(|c| c.is_ascii_digit())
+            {
+                continue;
+            }
+            words.extend(
+                token
+                    .split(|c: char| !c.is_alphabetic() && c.ne(&'\''))
+                    .map(|word| word.trim_matches('\''))
+                    .filter(|word| word.chars().count().gt(&1))
+                    .map(String::from),
+            );
+        }
+    }
+    words
+}
+
+///
+/// The word under the cursor with its first and last columns, in chars.
+///
+pub fn word_at(textarea: &TextArea<'_>) -> Option<(String, usize, usize)> {
+    let (row, col) = textarea.cursor();
+    let chars: Vec<char> = textarea.lines()[row].chars().collect();
+    let part = |c: &char| c.is_alphabetic() || c.eq(&'\'');
+    let start = chars[..col.min(chars.len())]
+        .iter()
+        .rposition(|c| !part(c))
+        .map_or(0, |i| i + 1);
+    let end = chars[start..]
+        .iter()
+        .position(|c| !part(c))
+        .map_or(chars.len(), |i| start + i);
+    let word: String = chars[start..end].iter().collect();
+    let word = word.trim_matches('\'').to_string();
+    (!word.is_empty()).then_some((word, start, end))
+}
+
+///
+/// Replace the word under the cursor with `replacement`.
+///
+pub fn replace_word(textarea: &mut TextArea<'_>, replacement: &str) {
+    let Some((word, start, end)) = word_at(textarea) else {
+        return;
+    };
+    let (row, _) = textarea.cursor();
+    let mut lines = textarea.lines().to_vec();
+    let chars: Vec<char> = lines[row].chars().collect();
+    let before: String = chars[..start].iter().collect();
+    let after: String = chars[end..].iter().collect();
+    let leading = chars[start..end]
+        .iter()
+        .take_while(|c| c.eq(&&'\''))
+        .count();
+    let trailing = chars[start..end].len() - leading - word.chars().count();
+    lines[row] = format!(
+        "{before}{}{replacement}{}{after}",
+        "'".repeat(leading),
+        "'".repeat(trailing)
+    );
+    set_text(textarea, &lines.join("\n"));
+    textarea.move_cursor(CursorMove::Jump(
+        u16::try_from(row).unwrap_or(u16::MAX),
+        u16::try_from(start + leading + replacement.chars().count()).unwrap_or(u16::MAX),
+    ));
+}
+
+pub enum SuggestionAction {
+    Stay,
+    Close,
+    Replace(String),
+    Add(String),
+}
+
+///
+/// The corrections offered for a misspelled word.
+///
+pub struct Suggestions {
+    word: String,
+    suggestions: Vec<String>,
+    picker: Picker,
+}
+
+impl Suggestions {
+    pub fn new(word: String, suggestions: Vec<String>) -> Self {
+        Self {
+            picker: Picker::new(&word, " ⏎ choose  esc close ", 60),
+            word,
+            suggestions,
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> SuggestionAction {
+        match self.picker.handle(key, self.suggestions.len() + 1) {
+            PickerAction::Stay => SuggestionAction::Stay,
+            PickerAction::Close => SuggestionAction::Close,
+            PickerAction::Choose(i) => self.suggestions.get(i).map_or_else(
+                || SuggestionAction::Add(self.word.clone()),
+                |suggestion| SuggestionAction::Replace(suggestion.clone()),
+            ),
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let mut items: Vec<ListItem> = self
+            .suggestions
+            .iter()
+            .map(|suggestion| ListItem::new(suggestion.as_str()))
+            .collect();
+        items.push(ListItem::new(format!(
+            "Add \"{}\" to the repository words",
+            self.word
+        )));
+        self.picker.render(f, theme, items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+NEEDAFFIX X
+FORBIDDENWORD F
+PFX U Y 1
+PFX U 0 un .
+SFX S Y 2
+SFX S y ies [^aeiou]y
+SFX S 0 s [^y]
+SFX D N 1
+SFX D 0 ed [^e]
+";
+
+    const DIC: &str = "5
+happy/U
+city/S
+do/US
+walk/D
+kind/X
+wrong/F
+";
+
+    fn dictionary(name: &str) -> Dictionary {
+        let dir = env::temp_dir().join(format!("rei-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("test");
+        fs::write(base.with_extension("aff"), AFF).unwrap();
+        fs::write(base.with_extension("dic"), DIC).unwrap();
+        let dictionary = Dictionary::find(base.to_str()).unwrap().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        dictionary
+    }
+
+    #[test]
+    fn affixes() {
+        let dictionary = dictionary("affixes");
+        for word in ["happy", "unhappy", "cities", "walked", "dos", "undos"] {
+            assert!(dictionary.check(word), "{word}");
+        }
+        for word in ["citys", "unwalked", "happies", "kind", "wrong", "walkeds"] {
+            assert!(!dictionary.check(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn case() {
+        let dictionary = dictionary("case");
+        assert!(dictionary.check("Happy"));
+        assert!(dictionary.check("CITIES"));
+        assert!(!dictionary.check("hAPPY"));
+    }
+
+    #[test]
+    fn conditions() {
+        let conditions = Condition::parse("[^aeiou]y.");
+        assert_eq!(conditions.len(), 3);
+        assert!(conditions[0].matches('t'));
+        assert!(!conditions[0].matches('a'));
+        assert!(conditions[1].matches('y'));
+        assert!(conditions[2].matches('z'));
+    }
+}
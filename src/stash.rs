@@ -0,0 +1,260 @@
+use crate::git::{self, Stash};
+use crate::prompt::{Confirm, Prompt, PromptAction};
+use crate::{render_message, App};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+///
+/// What the screen waits for before running git.
+///
+enum Pending {
+    Create { prompt: Prompt, untracked: bool },
+    Branch(Prompt),
+    Drop(Confirm),
+}
+
+pub enum StashesAction {
+    Stay,
+    Back,
+}
+
+///
+/// The stashes of the repository with the changes of the selected one.
+///
+pub struct Stashes {
+    stashes: Vec<Stash>,
+    selected: usize,
+    diff: String,
+    scroll: u16,
+    ///
+    /// The message proposed for a new stash, the title of the form.
+    ///
+    title: String,
+    pending: Option<Pending>,
+    notice: Option<String>,
+    message: Option<String>,
+}
+
+impl Stashes {
+    pub fn new(title: String) -> Self {
+        let mut stashes = Self {
+            stashes: git::stashes(),
+            selected: 0,
+            diff: String::new(),
+            scroll: 0,
+            title,
+            pending: None,
+            notice: None,
+            message: None,
+        };
+        stashes.select(0);
+        stashes
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.stashes.len().saturating_sub(1));
+        self.scroll = 0;
+        self.diff = self
+            .stashes
+            .get(self.selected)
+            .map_or_else(String::new, |stash| git::stash_diff(&stash.name));
+    }
+
+    ///
+    /// Run git then reload the stashes, `done` is shown when git says
+    /// nothing.
+    ///
+    fn git(&mut self, args: &[&str], done: &str) {
+        match git::notice(args, done) {
+            Ok(notice) => self.notice = Some(notice),
+            Err(output) => self.message = Some(output),
+        }
+        self.stashes = git::stashes();
+        self.select(self.selected);
+    }
+
+    fn name(&self) -> Option<String> {
+        self.stashes
+            .get(self.selected)
+            .map(|stash| stash.name.clone())
+    }
+
+    fn create(&mut self, message: &str, untracked: bool) {
+        let mut args = vec!["stash", "push"];
+        if untracked {
+            args.push("--include-untracked");
+        }
+        if !message.is_empty() {
+            args.extend(["--message", message]);
+        }
+        self.selected = 0;
+        self.git(&args, "Stashed the changes.");
+    }
+
+    fn handle_pending(&mut self, mut pending: Pending, key: KeyEvent) {
+        let answer = match &mut pending {
+            Pending::Create { prompt, .. } => match prompt.handle(key) {
+                PromptAction::Stay => None,
+                PromptAction::Cancel => Some(None),
+                PromptAction::Submit(value) => Some(Some(value)),
+            },
+            Pending::Branch(prompt) => match prompt.handle(key) {
+                PromptAction::Stay => None,
+                PromptAction::Submit(value) if !value.is_empty() => {
+                    match git::branch_name(&value) {
+                        Ok(value) => Some(Some(value)),
+                        Err(output) => {
+                            self.message = Some(output);
+                            Some(None)
+                        }
+                    }
+                }
+                PromptAction::Cancel | PromptAction::Submit(_) => Some(None),
+            },
+            Pending::Drop(_) => Confirm::handle(key).map(|yes| yes.then(String::new)),
+        };
+        let Some(Some(value)) = answer else {
+            if answer.is_none() {
+                self.pending = Some(pending);
+            }
+            return;
+        };
+        match pending {
+            Pending::Create { untracked, .. } => self.create(&value, untracked),
+            Pending::Branch(_) => {
+                if let Some(name) = self.name() {
+                    self.git(
+                        &["stash", "branch", &value, &name],
+                        &format!("Switched to {value}."),
+                    );
+                }
+            }
+            Pending::Drop(_) => {
+                if let Some(name) = self.name() {
+                    self.git(&["stash", "drop", &name], &format!("Dropped {name}."));
+                }
+            }
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> StashesAction {
+        self.notice = None;
+        if self.message.take().is_some() {
+            return StashesAction::Stay;
+        }
+        if let Some(pending) = self.pending.take() {
+            self.handle_pending(pending, key);
+            return StashesAction::Stay;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return StashesAction::Back,
+            KeyCode::Down | KeyCode::Char('j') => self.select(self.selected + 1),
+            KeyCode::Up | KeyCode::Char('k') => self.select(self.selected.saturating_sub(1)),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char(c @ ('n' | 'N')) => {
+                let untracked = c.eq(&'N');
+                let title = if untracked {
+                    "Stash with untracked files"
+                } else {
+                    "Stash"
+                };
+                self.pending = Some(Pending::Create {
+                    prompt: Prompt::new(title, &self.title),
+                    untracked,
+                });
+            }
+            KeyCode::Enter | KeyCode::Char('a') => {
+                if let Some(name) = self.name() {
+                    self.git(&["stash", "apply", &name], &format!("Applied {name}."));
+                }
+            }
+            KeyCode::Char('p') => {
+                if let Some(name) = self.name() {
+                    self.git(&["stash", "pop", &name], &format!("Popped {name}."));
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(name) = self.name() {
+                    self.pending = Some(Pending::Drop(Confirm::new(&format!(
+                        "Drop {name}? Its changes will be lost."
+                    ))));
+                }
+            }
+            KeyCode::Char('b') => {
+                if let Some(name) = self.name() {
+                    let title = format!("Branch from {name}");
+                    self.pending = Some(Pending::Branch(Prompt::new(&title, "")));
+                }
+            }
+            _ => {}
+        }
+        StashesAction::Stay
+    }
+
+    pub fn render(&self, f: &mut Frame, app: &App) {
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_type(BorderType::Rounded)
+            .title(" Stash ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(" n new  N with untracked  ⏎ apply  p pop  d drop  b branch  esc back ")
+            .title_alignment(Alignment::Center)
+            .style(app.theme.text());
+        let inner = block.inner(f.area());
+        f.render_widget(block, f.area());
+        let [list, diff, notice] = Layout::vertical([
+            Constraint::Percentage(30),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        let items: Vec<ListItem> = if self.stashes.is_empty() {
+            vec![ListItem::new("No stash.").style(app.theme.inactive())]
+        } else {
+            self.stashes
+                .iter()
+                .map(|stash| {
+                    ListItem::new(format!(
+                        "{}  {}  ({})",
+                        stash.name, stash.subject, stash.date
+                    ))
+                })
+                .collect()
+        };
+        let mut state =
+            ListState::default().with_selected((!self.stashes.is_empty()).then_some(self.selected));
+        f.render_stateful_widget(
+            List::new(items).highlight_style(app.theme.selection()),
+            list,
+            &mut state,
+        );
+        f.render_widget(
+            Paragraph::new(self.diff.as_str())
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0))
+                .block(
+                    Block::default()
+                        .borders(Borders::TOP)
+                        .title(" Changes ")
+                        .title_bottom(" pageup pagedown scroll "),
+                ),
+            diff,
+        );
+        if let Some(text) = &self.notice {
+            f.render_widget(Paragraph::new(text.as_str()), notice);
+        }
+        match &self.pending {
+            Some(Pending::Create { prompt, .. } | Pending::Branch(prompt)) => {
+                prompt.render(f, &app.theme);
+            }
+            Some(Pending::Drop(confirm)) => confirm.render(f, &app.theme),
+            None => {}
+        }
+        if let Some(message) = &self.message {
+            render_message(f, message);
+        }
+    }
+}
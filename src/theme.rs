@@ -0,0 +1,185 @@
+use crate::config::{Config, ThemeColors};
+use ratatui::style::{Color, Modifier, Style};
+use std::env;
+use std::str::FromStr;
+
+///
+/// The colours of the interface.
+///
+/// Without colour (`NO_COLOR` set) every style falls back to the terminal
+/// colours and the states are told apart by modifiers only.
+///
+#[derive(Clone)]
+pub struct Theme {
+    pub text: Color,
+    pub inactive: Color,
+    pub too_long: Color,
+    pub long: Color,
+    pub medium: Color,
+    pub short: Color,
+    pub selection: Color,
+    pub selected: Color,
+    pub monochrome: bool,
+}
+
+///
+/// How close the longest line of a field is to the limit.
+///
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Length {
+    Empty,
+    Short,
+    Medium,
+    Long,
+    TooLong,
+}
+
+impl Theme {
+    pub const fn dark() -> Self {
+        Self {
+            text: Color::White,
+            inactive: Color::DarkGray,
+            too_long: Color::Red,
+            long: Color::Rgb(255, 165, 0),
+            medium: Color::Yellow,
+            short: Color::Green,
+            selection: Color::White,
+            selected: Color::Black,
+            monochrome: false,
+        }
+    }
+
+    pub const fn light() -> Self {
+        Self {
+            text: Color::Black,
+            inactive: Color::Gray,
+            too_long: Color::Rgb(200, 0, 0),
+            long: Color::Rgb(205, 102, 0),
+            medium: Color::Rgb(160, 130, 0),
+            short: Color::Rgb(0, 128, 0),
+            selection: Color::Black,
+            selected: Color::White,
+            monochrome: false,
+        }
+    }
+
+    pub const fn high_contrast() -> Self {
+        Self {
+            text: Color::White,
+            inactive: Color::Gray,
+            too_long: Color::LightRed,
+            long: Color::LightMagenta,
+            medium: Color::LightYellow,
+            short: Color::LightGreen,
+            selection: Color::LightYellow,
+            selected: Color::Black,
+            monochrome: false,
+        }
+    }
+
+    pub const fn monochrome() -> Self {
+        Self {
+            text: Color::Reset,
+            inactive: Color::Reset,
+            too_long: Color::Reset,
+            long: Color::Reset,
+            medium: Color::Reset,
+            short: Color::Reset,
+            selection: Color::Reset,
+            selected: Color::Reset,
+            monochrome: true,
+        }
+    }
+
+    fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The theme chosen in the configuration, a user theme starts from its
+    /// `base` and overrides the colours it defines.
+    ///
+    /// # Errors
+    ///
+    /// On unknown theme or invalid colour.
+    ///
+    pub fn new(config: &Config) -> Result<Self, String> {
+        if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return Ok(Self::monochrome());
+        }
+        let name = config.theme.name.as_str();
+        if let Some(theme) = Self::builtin(name) {
+            return Ok(theme);
+        }
+        let colors = config
+            .themes
+            .get(name)
+            .ok_or_else(|| format!("unknown theme `{name}`"))?;
+        let base = colors.base.as_deref().unwrap_or("dark");
+        let mut theme =
+            Self::builtin(base).ok_or_else(|| format!("unknown base theme `{base}`"))?;
+        theme.apply(colors)?;
+        Ok(theme)
+    }
+
+    fn apply(&mut self, colors: &ThemeColors) -> Result<(), String> {
+        let slots = [
+            (&colors.text, &mut self.text),
+            (&colors.inactive, &mut self.inactive),
+            (&colors.too_long, &mut self.too_long),
+            (&colors.long, &mut self.long),
+            (&colors.medium, &mut self.medium),
+            (&colors.short, &mut self.short),
+            (&colors.selection, &mut self.selection),
+            (&colors.selected, &mut self.selected),
+        ];
+        for (value, slot) in slots {
+            if let Some(value) = value {
+                *slot = Color::from_str(value).map_err(|_| format!("invalid colour `{value}`"))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn text(&self) -> Style {
+        Style::default().fg(self.text)
+    }
+
+    pub fn inactive(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::DIM)
+        } else {
+            Style::default().fg(self.inactive)
+        }
+    }
+
+    pub fn length(&self, length: Length) -> Style {
+        let color = match length {
+            Length::Empty => self.text,
+            Length::Short => self.short,
+            Length::Medium => self.medium,
+            Length::Long => self.long,
+            Length::TooLong => self.too_long,
+        };
+        if self.monochrome && length.eq(&Length::TooLong) {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else if self.monochrome {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        }
+    }
+
+    pub fn selection(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.selected).bg(self.selection)
+        }
+    }
+}
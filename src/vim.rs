@@ -0,0 +1,262 @@
+use crate::theme::Theme;
+use crossterm::event::KeyEvent;
+use ratatui::style::{Color, Modifier, Style};
+use std::fmt::{Display, Formatter};
+use tui_textarea::{CursorMove, Input, Key, Scrolling, TextArea};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    Insert,
+    Visual,
+    Operator(char),
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "NORMAL"),
+            Self::Insert => write!(f, "INSERT"),
+            Self::Visual => write!(f, "VISUAL"),
+            Self::Operator(c) => write!(f, "OPERATOR({c})"),
+        }
+    }
+}
+
+impl Mode {
+    pub fn cursor_style(self, theme: &Theme) -> Style {
+        if theme.monochrome {
+            return Style::default().add_modifier(Modifier::REVERSED);
+        }
+        let color = match self {
+            Self::Normal => Color::Reset,
+            Self::Insert => Color::LightBlue,
+            Self::Visual => Color::LightYellow,
+            Self::Operator(_) => Color::LightGreen,
+        };
+        Style::default().fg(color).add_modifier(Modifier::REVERSED)
+    }
+}
+
+///
+/// A modal editing layer over the textareas of the form.
+///
+/// The register is shared by all the fields, a yank in one field can be
+/// pasted in another one.
+///
+pub struct Vim {
+    mode: Mode,
+    pending: Input,
+    register: String,
+}
+
+impl Vim {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Normal,
+            pending: Input::default(),
+            register: String::new(),
+        }
+    }
+
+    pub const fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    ///
+    /// Whether the key must reach vim before the key bindings, `Esc` leaves
+    /// the insert and visual modes instead of quitting the form.
+    ///
+    pub fn captures(&self, key: KeyEvent) -> bool {
+        self.mode.ne(&Mode::Normal) && Input::from(key).key.eq(&Key::Esc)
+    }
+
+    pub fn handle(&mut self, key: KeyEvent, textarea: &mut TextArea<'_>) {
+        let input = Input::from(key);
+        if input.key.eq(&Key::Null) {
+            return;
+        }
+        let pending = std::mem::take(&mut self.pending);
+        if self.mode.eq(&Mode::Insert) {
+            if input.key.eq(&Key::Esc) || (input.key.eq(&Key::Char('c')) && input.ctrl) {
+                self.mode = Mode::Normal;
+            } else {
+                textarea.input(input);
+            }
+            return;
+        }
+        if let Some(mode) = self.command(input, pending, textarea) {
+            self.mode = mode;
+            return;
+        }
+        match self.mode {
+            Mode::Operator('y') => {
+                textarea.copy();
+                self.register = textarea.yank_text();
+                self.mode = Mode::Normal;
+            }
+            Mode::Operator('d') => {
+                textarea.cut();
+                self.register = textarea.yank_text();
+                self.mode = Mode::Normal;
+            }
+            Mode::Operator('c') => {
+                textarea.cut();
+                self.register = textarea.yank_text();
+                self.mode = Mode::Insert;
+            }
+            _ => {}
+        }
+    }
+
+    ///
+    /// Run a normal or visual mode command, gives the next mode when the
+    /// command ends the pending operator.
+    ///
+    fn command(
+        &mut self,
+        input: Input,
+        pending: Input,
+        textarea: &mut TextArea<'_>,
+    ) -> Option<Mode> {
+        let operator = matches!(self.mode, Mode::Operator(_));
+        match (input.key, input.ctrl) {
+            (Key::Char('h') | Key::Left, false) => textarea.move_cursor(CursorMove::Back),
+            (Key::Char('j') | Key::Down, false) => textarea.move_cursor(CursorMove::Down),
+            (Key::Char('k') | Key::Up, false) => textarea.move_cursor(CursorMove::Up),
+            (Key::Char('l') | Key::Right, false) => textarea.move_cursor(CursorMove::Forward),
+            (Key::Char('w'), false) => textarea.move_cursor(CursorMove::WordForward),
+            (Key::Char('e'), false) => {
+                textarea.move_cursor(CursorMove::WordEnd);
+                if operator {
+                    textarea.move_cursor(CursorMove::Forward);
+                }
+            }
+            (Key::Char('b'), false) => textarea.move_cursor(CursorMove::WordBack),
+            (Key::Char('^' | '0'), false) => textarea.move_cursor(CursorMove::Head),
+            (Key::Char('$'), false) => textarea.move_cursor(CursorMove::End),
+            (Key::Char('}'), false) => textarea.move_cursor(CursorMove::ParagraphForward),
+            (Key::Char('{'), false) => textarea.move_cursor(CursorMove::ParagraphBack),
+            (Key::Char('g'), false) if pending.key.eq(&Key::Char('g')) => {
+                textarea.move_cursor(CursorMove::Top);
+            }
+            (Key::Char('G'), false) => textarea.move_cursor(CursorMove::Bottom),
+            (Key::Char('e'), true) => textarea.scroll((1, 0)),
+            (Key::Char('y'), true) => textarea.scroll((-1, 0)),
+            (Key::Char('d'), true) => textarea.scroll(Scrolling::HalfPageDown),
+            (Key::Char('u'), true) => textarea.scroll(Scrolling::HalfPageUp),
+            (Key::Char('f'), true) => textarea.scroll(Scrolling::PageDown),
+            (Key::Char('b'), true) => textarea.scroll(Scrolling::PageUp),
+            (Key::Char('D'), false) => {
+                textarea.delete_line_by_end();
+                self.register = textarea.yank_text();
+                return Some(Mode::Normal);
+            }
+            (Key::Char('C'), false) => {
+                textarea.delete_line_by_end();
+                self.register = textarea.yank_text();
+                textarea.cancel_selection();
+                return Some(Mode::Insert);
+            }
+            (Key::Char('p'), false) => {
+                textarea.set_yank_text(self.register.clone());
+                textarea.paste();
+                return Some(Mode::Normal);
+            }
+            (Key::Char('u'), false) => {
+                textarea.undo();
+                return Some(Mode::Normal);
+            }
+            (Key::Char('r'), true) => {
+                textarea.redo();
+                return Some(Mode::Normal);
+            }
+            (Key::Char('x'), false) => {
+                textarea.delete_next_char();
+                return Some(Mode::Normal);
+            }
+            (Key::Char('i'), false) => {
+                textarea.cancel_selection();
+                return Some(Mode::Insert);
+            }
+            (Key::Char('a'), false) => {
+                textarea.cancel_selection();
+                textarea.move_cursor(CursorMove::Forward);
+                return Some(Mode::Insert);
+            }
+            (Key::Char('A'), false) => {
+                textarea.cancel_selection();
+                textarea.move_cursor(CursorMove::End);
+                return Some(Mode::Insert);
+            }
+            (Key::Char('I'), false) => {
+                textarea.cancel_selection();
+                textarea.move_cursor(CursorMove::Head);
+                return Some(Mode::Insert);
+            }
+            (Key::Char('o'), false) => {
+                textarea.move_cursor(CursorMove::End);
+                textarea.insert_newline();
+                return Some(Mode::Insert);
+            }
+            (Key::Char('O'), false) => {
+                textarea.move_cursor(CursorMove::Head);
+                textarea.insert_newline();
+                textarea.move_cursor(CursorMove::Up);
+                return Some(Mode::Insert);
+            }
+            (Key::Char('v'), false) if self.mode.eq(&Mode::Normal) => {
+                textarea.start_selection();
+                return Some(Mode::Visual);
+            }
+            (Key::Char('V'), false) if self.mode.eq(&Mode::Normal) => {
+                textarea.move_cursor(CursorMove::Head);
+                textarea.start_selection();
+                textarea.move_cursor(CursorMove::End);
+                return Some(Mode::Visual);
+            }
+            (Key::Esc | Key::Char('v'), false) if self.mode.eq(&Mode::Visual) => {
+                textarea.cancel_selection();
+                return Some(Mode::Normal);
+            }
+            (Key::Esc, false) => {
+                textarea.cancel_selection();
+                return Some(Mode::Normal);
+            }
+            (Key::Char(c), false) if self.mode.eq(&Mode::Operator(c)) => {
+                // yy, dd and cc work on the whole line
+                textarea.move_cursor(CursorMove::Head);
+                textarea.start_selection();
+                let cursor = textarea.cursor();
+                textarea.move_cursor(CursorMove::Down);
+                if cursor.eq(&textarea.cursor()) {
+                    textarea.move_cursor(CursorMove::End);
+                }
+            }
+            (Key::Char(op @ ('y' | 'd' | 'c')), false) if self.mode.eq(&Mode::Normal) => {
+                textarea.start_selection();
+                return Some(Mode::Operator(op));
+            }
+            (Key::Char(op @ ('y' | 'd' | 'c')), false) if self.mode.eq(&Mode::Visual) => {
+                // the visual selection is inclusive
+                textarea.move_cursor(CursorMove::Forward);
+                if op.eq(&'y') {
+                    textarea.copy();
+                } else {
+                    textarea.cut();
+                }
+                self.register = textarea.yank_text();
+                return Some(if op.eq(&'c') {
+                    Mode::Insert
+                } else {
+                    Mode::Normal
+                });
+            }
+            _ => {
+                self.pending = input;
+                return Some(self.mode);
+            }
+        }
+        None
+    }
+}